@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -15,7 +16,136 @@ pub struct Acceleration {
     pub acceleration: Vec3,
 }
 
+// Somme des `ApplyForceEvent` reçus pendant la frame, appliquée à chaque sous-étape
+#[derive(Component, Default)]
+pub struct ExternalForce {
+    pub force: Vec3,
+}
+
+// Phase de vol ; seuls les boids en vol participent au flocking
+#[derive(Component, Clone, Copy, PartialEq, Debug, Default)]
+pub enum FlightState {
+    #[default]
+    Flying,
+    // Approche du point de perche, sans vitesse minimale
+    Landing { perch: Vec3 },
+    // Immobile jusqu'à l'instant `until` (temps écoulé, en secondes)
+    Perched { until: f32 },
+    // Envol : flocking actif, mais pas encore repris par le confinement
+    TakingOff { until: f32 },
+}
+
+impl FlightState {
+    pub fn is_airborne(&self) -> bool {
+        matches!(self, FlightState::Flying | FlightState::TakingOff { .. })
+    }
+}
+
+// Réserve d'énergie dans [0, 1], vidée par l'effort et rechargée en plané ou posé
+#[derive(Component)]
+pub struct Energy {
+    pub value: f32,
+    // Le boid profite de l'aspiration d'un voisin devant lui
+    pub drafting: bool,
+}
+
+// Réaction d'effarouchement, transmise de voisin en voisin
+#[derive(Component, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Startle {
+    #[default]
+    Calm,
+    // Réaction copiée d'un voisin, déclenchée à l'instant `at`
+    Pending { at: f32, escape: Vec3, wave: u32 },
+    // Fuite à grande vitesse dans la direction `escape`
+    Escaping { until: f32, escape: Vec3, wave: u32 },
+    // Insensible jusqu'à `until`, pour qu'une vague ne revienne pas sur ses pas
+    Recovering { until: f32 },
+}
+
+// Multiplicateurs individuels des paramètres de `BoidSettings`, tirés à l'apparition
+#[derive(Component, Clone, Copy, Debug)]
+pub struct BoidTraits {
+    pub speed: f32,
+    pub field_of_view: f32,
+    pub range: f32,
+    pub cohesion: f32,
+    pub alignment: f32,
+    pub separation: f32,
+}
+
+impl Default for BoidTraits {
+    fn default() -> Self {
+        BoidTraits {
+            speed: 1.0,
+            field_of_view: 1.0,
+            range: 1.0,
+            cohesion: 1.0,
+            alignment: 1.0,
+            separation: 1.0,
+        }
+    }
+}
+
+// États récents du boid (instant, position, vélocité), lus avec retard par ses voisins
+#[derive(Component, Default)]
+pub struct PerceptionHistory {
+    pub samples: VecDeque<(f32, Vec3, Vec3)>,
+}
+
+// Mémoire de l'intégrateur entre deux phases ou deux étapes
+#[derive(Component, Default)]
+pub struct IntegratorState {
+    // Accélération de l'étape précédente (Verlet)
+    pub previous_acceleration: Vec3,
+    // État au début de l'étape et première pente (RK2)
+    pub start_position: Vec3,
+    pub start_velocity: Vec3,
+    pub start_acceleration: Vec3,
+}
+
+// Contribution de chaque règle à l'accélération, rangée dans l'ordre du `SteeringRegistry`
+#[derive(Component, Default, Clone, Debug)]
+pub struct SteeringForces {
+    pub forces: Vec<Vec3>,
+}
+
+impl SteeringForces {
+    pub fn total(&self) -> Vec3 {
+        self.forces.iter().sum()
+    }
+}
+
 #[derive(Component)]
 pub struct Obstacle {
     pub radius: f32,
+}
+
+// Indice du boid dans les tableaux du `FlockCore`
+#[derive(Component, Default)]
+pub struct FlockSlot(pub usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoidVisual {
+    Scene,
+    Instanced,
+    Animated,
+    LowPoly,
+    Point,
+}
+
+// Représentation actuellement attachée au boid
+#[derive(Component)]
+pub struct RenderedAs(pub BoidVisual);
+
+#[derive(Component)]
+pub struct WingFlap {
+    pub phase: f32,
+    pub amplitude: f32,
+    pub previous_velocity: Vec3,
+}
+
+// Aile articulée, enfant d'un boid ; `side` vaut 1.0 ou -1.0
+#[derive(Component)]
+pub struct Wing {
+    pub side: f32,
 }
\ No newline at end of file
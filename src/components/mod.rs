@@ -1,2 +1,3 @@
 pub mod boid;
-pub mod spatial;
\ No newline at end of file
+pub mod spatial;
+pub mod terrain;
\ No newline at end of file
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// Maillage du sol, reconstruit quand le relief change
+#[derive(Component)]
+pub struct TerrainMesh;
@@ -1,7 +1,23 @@
 use bevy::prelude::*;
 
+// Force ponctuelle ajoutée à l'accélération, en plus des `SteeringForces`
 #[derive(Event)]
 pub struct ApplyForceEvent {
     pub entity: Entity,
     pub force: Vec3,
-}
\ No newline at end of file
+}
+
+// Demande d'envol d'un boid posé, propagée par la cascade d'envol
+#[derive(Event)]
+pub struct StartlePerchedEvent;
+
+// Effarouche un boid en vol, qui fuit à l'opposé de `threat`
+#[derive(Event)]
+pub struct StartleEvent {
+    pub entity: Entity,
+    pub threat: Vec3,
+}
+
+// Retire les traits individuels de tous les boids avec les réglages courants
+#[derive(Event)]
+pub struct ResampleTraitsEvent;
@@ -3,15 +3,27 @@ mod events;
 mod globals;
 mod plugins;
 mod resources;
+mod steering;
 mod systems;
 mod ui;
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 
 use crate::plugins::boids::BoidsPlugin;
+use crate::plugins::camera::CameraControllerPlugin;
+use crate::plugins::debug::DebugOverlayPlugin;
+use crate::plugins::energy::EnergyPlugin;
+use crate::plugins::flock_core::FlockCorePlugin;
+use crate::plugins::formation::FormationPlugin;
+use crate::plugins::inspection::InspectionPlugin;
+use crate::plugins::leaders::LeadersPlugin;
+use crate::plugins::perching::PerchingPlugin;
+use crate::plugins::rendering::BoidRenderingPlugin;
 use crate::plugins::setup::SetupPlugin;
 use crate::plugins::spatial::SpatialPlugin;
-use bevy::{input::mouse::AccumulatedMouseMotion, prelude::*};
-use std::{f32::consts::FRAC_PI_2, ops::Range};
+use crate::plugins::startle::StartlePlugin;
+use crate::plugins::terrain::TerrainPlugin;
+use crate::plugins::wind::WindPlugin;
+use bevy::prelude::*;
 
 fn main() {
     App::new()
@@ -19,70 +31,18 @@ fn main() {
         .add_plugins(SetupPlugin)
         .add_plugins(BoidsPlugin)
         .add_plugins(SpatialPlugin)
+        .add_plugins(WindPlugin)
+        .add_plugins(TerrainPlugin)
+        .add_plugins(PerchingPlugin)
+        .add_plugins(EnergyPlugin)
+        .add_plugins(LeadersPlugin)
+        .add_plugins(FormationPlugin)
+        .add_plugins(StartlePlugin)
+        .add_plugins(FlockCorePlugin)
+        .add_plugins(BoidRenderingPlugin)
+        .add_plugins(CameraControllerPlugin)
+        .add_plugins(InspectionPlugin)
+        .add_plugins(DebugOverlayPlugin)
         .add_plugins(FrameTimeDiagnosticsPlugin::default())
-        .add_systems(Startup, setup)
-        .add_systems(Update, orbit)
-        .init_resource::<CameraSettings>()
         .run();
 }
-
-#[derive(Debug, Resource)]
-struct CameraSettings {
-    pub orbit_distance: f32,
-    pub pitch_speed: f32,
-    pub pitch_range: Range<f32>,
-    pub roll_speed: f32,
-    pub yaw_speed: f32,
-}
-
-impl Default for CameraSettings {
-    fn default() -> Self {
-        let pitch_limit = FRAC_PI_2 - 0.01;
-        Self {
-            orbit_distance: 250.0,
-            pitch_speed: 0.003,
-            pitch_range: -pitch_limit..pitch_limit,
-            roll_speed: 1.0,
-            yaw_speed: 0.004,
-        }
-    }
-}
-
-fn setup(mut commands: Commands) {
-    commands.spawn((
-        Name::new("Camera"),
-        Camera3d::default(),
-        Transform::from_xyz(200.0, 200.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
-    ));
-}
-
-fn orbit(
-    mut camera: Single<&mut Transform, With<Camera>>,
-    camera_settings: Res<CameraSettings>,
-    mouse_buttons: Res<ButtonInput<MouseButton>>,
-    mouse_motion: Res<AccumulatedMouseMotion>,
-    time: Res<Time>,
-) {
-    let delta = mouse_motion.delta;
-    let mut delta_roll = 0.0;
-
-    if mouse_buttons.pressed(MouseButton::Left) {
-        let delta_pitch = delta.y * camera_settings.pitch_speed;
-        let delta_yaw = delta.x * camera_settings.yaw_speed;
-
-        delta_roll *= camera_settings.roll_speed * time.delta_secs();
-
-        let (yaw, pitch, roll) = camera.rotation.to_euler(EulerRot::YXZ);
-
-        let pitch = (pitch + delta_pitch).clamp(
-            camera_settings.pitch_range.start,
-            camera_settings.pitch_range.end,
-        );
-        let roll = roll + delta_roll;
-        let yaw = yaw + delta_yaw;
-        camera.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
-
-        let target = Vec3::ZERO;
-        camera.translation = target - camera.forward() * camera_settings.orbit_distance;
-    }
-}
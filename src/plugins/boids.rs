@@ -1,7 +1,15 @@
-use crate::resources::settings::{BoidSettings, GroupsTargets};
-use crate::systems::boids::{spawn_boids, spawn_obstacles};
+use crate::resources::settings::{
+    BoidSettings, CollisionSettings, GroupsTargets, IntegrationSettings, OrientationSettings, PerceptionSettings, TraitSettings, WanderSettings,
+};
+use crate::systems::boids::{adjust_boid_count, resample_traits, spawn_boids, spawn_obstacles};
+use crate::systems::flock_core::{begin_ecs_step, ecs_backend, end_ecs_step, step_flock_core};
+use crate::resources::collision::CollisionStats;
+use crate::systems::collision::{begin_collision_frame, collisions_enabled, resolve_obstacle_collisions};
 use crate::systems::flocking::*;
-use crate::events::ApplyForceEvent;
+use crate::systems::integration::{final_phase, integrate_boids, run_flock_substeps, FlockStep};
+use crate::events::{ApplyForceEvent, ResampleTraitsEvent};
+use crate::steering::registry::{update_steering_behaviours, AddSteeringBehaviour, SteeringRegistry};
+use crate::steering::wander::Wander;
 use crate::ui::UiPlugin;
 use bevy::prelude::*;
 
@@ -11,15 +19,35 @@ impl Plugin for BoidsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(BoidSettings::default())
             .insert_resource(GroupsTargets::default())
+            .insert_resource(OrientationSettings::default())
+            .insert_resource(WanderSettings::default())
+            .insert_resource(IntegrationSettings::default())
+            .insert_resource(CollisionSettings::default())
+            .insert_resource(TraitSettings::default())
+            .insert_resource(PerceptionSettings::default())
+            .init_resource::<CollisionStats>()
+            .init_resource::<SteeringRegistry>()
+            .add_steering_behaviour(Wander::default())
             .add_event::<ApplyForceEvent>()
+            .add_event::<ResampleTraitsEvent>()
             .add_plugins(UiPlugin)
             .add_systems(Startup, (spawn_boids, spawn_obstacles))
-            .add_systems(Update, (
+            .add_systems(Update, (adjust_boid_count, resample_traits))
+            .add_systems(Update, begin_collision_frame.before(begin_ecs_step).before(step_flock_core))
+            .add_systems(Update, update_steering_behaviours.before(begin_ecs_step))
+            .add_systems(FlockStep, (
                 flocking_system,
-                avoid_obstacles,
                 apply_forces,
-                update_boids,
-                confine_boids,
-            ).chain());
+                integrate_boids,
+                resolve_obstacle_collisions.run_if(final_phase.and(collisions_enabled)),
+                confine_boids.run_if(final_phase),
+            ).chain())
+            .add_systems(Update, (
+                begin_ecs_step,
+                collect_external_forces,
+                run_flock_substeps,
+                end_ecs_step,
+            ).chain().run_if(ecs_backend))
+            .add_systems(Update, record_perception_history.after(end_ecs_step).run_if(ecs_backend.and(reaction_delayed)));
     }
 }
\ No newline at end of file
@@ -0,0 +1,22 @@
+use crate::resources::camera::{CameraMode, CameraSettings, CameraState};
+use crate::systems::camera::*;
+use bevy::prelude::*;
+
+pub struct CameraControllerPlugin;
+
+impl Plugin for CameraControllerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraSettings>()
+            .init_resource::<CameraState>()
+            .add_systems(Startup, setup_camera)
+            .add_systems(Update, (
+                camera_shortcuts,
+                refocus_on_double_click.run_if(camera_mode_is(CameraMode::Orbit)),
+                orbit.run_if(camera_mode_is(CameraMode::Orbit)),
+                free_fly.run_if(camera_mode_is(CameraMode::FreeFly)),
+                follow_boid.run_if(camera_mode_is(CameraMode::FollowBoid)),
+                follow_group.run_if(camera_mode_is(CameraMode::FollowGroup)),
+                cinematic.run_if(camera_mode_is(CameraMode::Cinematic)),
+            ).chain());
+    }
+}
@@ -0,0 +1,12 @@
+use crate::resources::settings::DebugOverlaySettings;
+use crate::systems::debug::*;
+use bevy::prelude::*;
+
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlaySettings>()
+            .add_systems(Update, (draw_boid_overlays, draw_world_overlays));
+    }
+}
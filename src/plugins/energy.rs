@@ -0,0 +1,23 @@
+use crate::resources::energy::EnergyStats;
+use crate::resources::settings::EnergySettings;
+use crate::steering::drafting::Drafting;
+use crate::steering::fatigue::FatigueCohesion;
+use crate::steering::registry::AddSteeringBehaviour;
+use crate::systems::energy::*;
+use crate::systems::flock_core::{ecs_backend, end_ecs_step};
+use bevy::prelude::*;
+
+pub struct EnergyPlugin;
+
+impl Plugin for EnergyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnergySettings>()
+            .init_resource::<EnergyStats>()
+            .add_steering_behaviour(Drafting::default())
+            .add_steering_behaviour(FatigueCohesion::default())
+            .add_systems(Update, (update_energy.run_if(energy_enabled), update_energy_stats)
+                .chain()
+                .after(end_ecs_step)
+                .run_if(ecs_backend));
+    }
+}
@@ -0,0 +1,21 @@
+use crate::resources::flock_core::{FlockCore, FlockStepTimer};
+use crate::systems::flock_core::*;
+use bevy::diagnostic::{Diagnostic, RegisterDiagnostic};
+use bevy::prelude::*;
+
+pub struct FlockCorePlugin;
+
+impl Plugin for FlockCorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlockCore>()
+            .init_resource::<FlockStepTimer>()
+            .register_diagnostic(Diagnostic::new(ECS_STEP_TIME).with_suffix(" ms"))
+            .register_diagnostic(Diagnostic::new(PACKED_STEP_TIME).with_suffix(" ms"))
+            .add_systems(Update, (
+                sync_flock_core,
+                step_flock_core,
+                mirror_flock_core,
+            ).chain().run_if(packed_backend))
+            .add_systems(Update, release_flock_core.run_if(ecs_backend));
+    }
+}
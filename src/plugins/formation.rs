@@ -0,0 +1,22 @@
+use crate::resources::formation::FormationState;
+use crate::resources::settings::FormationSettings;
+use crate::steering::formation::Formation;
+use crate::steering::registry::{update_steering_behaviours, AddSteeringBehaviour};
+use crate::systems::flock_core::ecs_backend;
+use crate::systems::formation::*;
+use crate::systems::leaders::elect_leaders;
+use bevy::prelude::*;
+
+pub struct FormationPlugin;
+
+impl Plugin for FormationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FormationSettings>()
+            .init_resource::<FormationState>()
+            .add_steering_behaviour(Formation::default())
+            .add_systems(Update, (
+                assign_formation_slots.after(elect_leaders).before(update_steering_behaviours),
+                draw_formation_slots,
+            ).run_if(ecs_backend));
+    }
+}
@@ -0,0 +1,16 @@
+use crate::resources::inspection::SelectedBoid;
+use crate::systems::inspection::*;
+use bevy::prelude::*;
+
+pub struct InspectionPlugin;
+
+impl Plugin for InspectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedBoid>()
+            .add_systems(Update, (
+                select_boid_on_click,
+                inspect_selected_boid,
+                highlight_selection,
+            ).chain());
+    }
+}
@@ -0,0 +1,19 @@
+use crate::resources::leaders::GroupLeaders;
+use crate::resources::settings::LeaderSettings;
+use crate::steering::leadership::Leadership;
+use crate::steering::registry::{update_steering_behaviours, AddSteeringBehaviour};
+use crate::systems::flock_core::ecs_backend;
+use crate::systems::leaders::*;
+use bevy::prelude::*;
+
+pub struct LeadersPlugin;
+
+impl Plugin for LeadersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LeaderSettings>()
+            .init_resource::<GroupLeaders>()
+            .add_steering_behaviour(Leadership::default())
+            .add_systems(Update, elect_leaders.before(update_steering_behaviours).run_if(leaders_enabled.and(ecs_backend)))
+            .add_systems(Update, draw_leaders.run_if(leaders_enabled.and(ecs_backend)));
+    }
+}
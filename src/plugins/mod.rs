@@ -1,3 +1,15 @@
 pub mod boids;
+pub mod camera;
+pub mod debug;
+pub mod energy;
+pub mod flock_core;
+pub mod formation;
+pub mod inspection;
+pub mod leaders;
+pub mod perching;
+pub mod rendering;
 pub mod setup;
-pub mod spatial;
\ No newline at end of file
+pub mod terrain;
+pub mod spatial;
+pub mod startle;
+pub mod wind;
\ No newline at end of file
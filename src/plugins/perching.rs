@@ -0,0 +1,26 @@
+use crate::events::StartlePerchedEvent;
+use crate::resources::perch::PerchLines;
+use crate::resources::settings::PerchSettings;
+use crate::systems::flock_core::{begin_ecs_step, ecs_backend, end_ecs_step};
+use crate::systems::flocking::apply_forces;
+use crate::systems::integration::{integrate_boids, FlockStep};
+use crate::systems::perching::*;
+use bevy::prelude::*;
+
+pub struct PerchingPlugin;
+
+impl Plugin for PerchingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PerchSettings>()
+            .init_resource::<PerchLines>()
+            .add_event::<StartlePerchedEvent>()
+            .add_systems(FlockStep, steer_landing.after(apply_forces).before(integrate_boids))
+            .add_systems(Update, (
+                decide_landing.run_if(perching_enabled),
+                cancel_perching.run_if(not(perching_enabled)),
+                startle_perched,
+            ).before(begin_ecs_step).run_if(ecs_backend))
+            .add_systems(Update, update_flight_states.after(end_ecs_step).run_if(ecs_backend))
+            .add_systems(Update, draw_perch_lines.run_if(perching_enabled));
+    }
+}
@@ -0,0 +1,20 @@
+use crate::resources::rendering::BirdAssets;
+use crate::resources::settings::{LodSettings, WingSettings};
+use crate::systems::rendering::*;
+use bevy::prelude::*;
+
+pub struct BoidRenderingPlugin;
+
+impl Plugin for BoidRenderingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BirdAssets>()
+            .init_resource::<LodSettings>()
+            .init_resource::<WingSettings>()
+            .add_systems(Update, (
+                build_bird_meshes,
+                sync_boid_rendering,
+                update_wing_flap,
+                animate_wings,
+            ).chain());
+    }
+}
@@ -0,0 +1,25 @@
+use crate::events::StartleEvent;
+use crate::resources::settings::StartleSettings;
+use crate::resources::startle::StartleWaves;
+use crate::steering::escape::Escape;
+use crate::steering::registry::{update_steering_behaviours, AddSteeringBehaviour};
+use crate::systems::flock_core::ecs_backend;
+use crate::systems::startle::*;
+use bevy::prelude::*;
+
+pub struct StartlePlugin;
+
+impl Plugin for StartlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StartleSettings>()
+            .init_resource::<StartleWaves>()
+            .add_event::<StartleEvent>()
+            .add_steering_behaviour(Escape::default())
+            .add_systems(Update, (
+                (startle_on_click, startle_randomly, startle_near_camera),
+                propagate_startles,
+            ).chain().before(update_steering_behaviours).run_if(startle_enabled.and(ecs_backend)))
+            .add_systems(Update, calm_startles.run_if(not(startle_enabled)))
+            .add_systems(Update, draw_startles.run_if(startle_enabled.and(ecs_backend)));
+    }
+}
@@ -0,0 +1,17 @@
+use crate::resources::settings::TerrainSettings;
+use crate::resources::terrain::TerrainHeightmap;
+use crate::steering::altitude::AltitudeKeeping;
+use crate::steering::registry::AddSteeringBehaviour;
+use crate::systems::terrain::rebuild_terrain;
+use bevy::prelude::*;
+
+pub struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TerrainSettings>()
+            .init_resource::<TerrainHeightmap>()
+            .add_steering_behaviour(AltitudeKeeping::default())
+            .add_systems(Update, rebuild_terrain);
+    }
+}
@@ -0,0 +1,13 @@
+use crate::resources::wind::WindField;
+use crate::steering::registry::AddSteeringBehaviour;
+use crate::steering::wind::Wind;
+use bevy::prelude::*;
+
+pub struct WindPlugin;
+
+impl Plugin for WindPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WindField>()
+            .add_steering_behaviour(Wind::default());
+    }
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use std::{f32::consts::FRAC_PI_2, ops::Range};
+use crate::globals::HEIGHT;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CameraMode {
+    // Rotation au clic gauche, zoom à la molette, déplacement au clic milieu
+    Orbit,
+    // Déplacement libre ZQSD/WASD, regard au clic gauche
+    FreeFly,
+    // Caméra de poursuite derrière un boid
+    FollowBoid,
+    // Orbite autour du barycentre d'un groupe
+    FollowGroup,
+    // Plans automatiques qui s'enchaînent
+    Cinematic,
+}
+
+impl CameraMode {
+    pub const ALL: [CameraMode; 5] = [
+        CameraMode::Orbit,
+        CameraMode::FreeFly,
+        CameraMode::FollowBoid,
+        CameraMode::FollowGroup,
+        CameraMode::Cinematic,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CameraMode::Orbit => "Orbite",
+            CameraMode::FreeFly => "Vol libre",
+            CameraMode::FollowBoid => "Suivre un boid",
+            CameraMode::FollowGroup => "Suivre un groupe",
+            CameraMode::Cinematic => "Cinématique",
+        }
+    }
+}
+
+#[derive(Debug, Resource)]
+pub struct CameraSettings {
+    pub mode: CameraMode,
+    pub orbit_target: Vec3,
+    pub orbit_distance: f32,
+    pub orbit_distance_range: Range<f32>,
+    pub zoom_speed: f32,
+    pub pan_speed: f32,
+    pub pitch_speed: f32,
+    pub pitch_range: Range<f32>,
+    pub yaw_speed: f32,
+    pub fly_speed: f32,
+    pub fly_boost: f32,
+    pub follow_distance: f32,
+    pub follow_height: f32,
+    // Vitesse de rattrapage de la caméra en suivi (1/s)
+    pub follow_smoothing: f32,
+    pub followed_group: u8,
+    // Durée d'un plan en mode cinématique (s)
+    pub shot_duration: f32,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        let pitch_limit = FRAC_PI_2 - 0.01;
+        Self {
+            mode: CameraMode::Orbit,
+            orbit_target: Vec3::new(0.0, HEIGHT / 2.0, 0.0),
+            orbit_distance: 250.0,
+            orbit_distance_range: 10.0..800.0,
+            zoom_speed: 0.1,
+            pan_speed: 0.0015,
+            pitch_speed: 0.003,
+            pitch_range: -pitch_limit..pitch_limit,
+            yaw_speed: 0.004,
+            fly_speed: 60.0,
+            fly_boost: 3.0,
+            follow_distance: 12.0,
+            follow_height: 3.0,
+            follow_smoothing: 5.0,
+            followed_group: 0,
+            shot_duration: 6.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CinematicShot {
+    // Lente orbite autour de la nuée
+    WideOrbit,
+    // Poursuite d'un boid
+    Chase,
+    // Point fixe au bord de la zone qui suit un boid au passage
+    FlyBy,
+    // Vue plongeante sur la nuée
+    TopDown,
+}
+
+#[derive(Resource)]
+pub struct CameraState {
+    pub followed_boid: Option<Entity>,
+    // Point visé lissé pour l'orbite et le suivi de groupe
+    pub focus: Vec3,
+    // Instant du dernier clic gauche, pour détecter le double clic
+    pub last_click: Option<f32>,
+    pub shot: CinematicShot,
+    pub shot_timer: Timer,
+    pub shot_anchor: Vec3,
+    pub shot_angle: f32,
+}
+
+impl Default for CameraState {
+    fn default() -> Self {
+        CameraState {
+            followed_boid: None,
+            focus: Vec3::ZERO,
+            last_click: None,
+            shot: CinematicShot::WideOrbit,
+            shot_timer: Timer::from_seconds(0.0, TimerMode::Once),
+            shot_anchor: Vec3::ZERO,
+            shot_angle: 0.0,
+        }
+    }
+}
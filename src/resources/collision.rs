@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+/// Contacts boid-obstacle résolus par la contrainte dure.
+#[derive(Resource, Default)]
+pub struct CollisionStats {
+    // Contacts de la frame en cours, toutes sous-étapes confondues
+    pub frame: u32,
+    pub total: u64,
+}
+
+impl CollisionStats {
+    pub fn record(&mut self, collisions: u32) {
+        self.frame += collisions;
+        self.total += collisions as u64;
+    }
+}
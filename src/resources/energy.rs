@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+/// Mesures agrégées du modèle d'énergie, recalculées à chaque frame.
+#[derive(Resource, Default)]
+pub struct EnergyStats {
+    pub mean: f32,
+    pub min: f32,
+    // Part des boids sous le seuil d'épuisement
+    pub exhausted: f32,
+    // Part des boids en vol profitant d'une aspiration
+    pub drafting: f32,
+    // Énergie moyenne par groupe
+    pub group_means: Vec<f32>,
+}
@@ -0,0 +1,472 @@
+use bevy::math::Vec3A;
+use bevy::prelude::*;
+use rayon::prelude::*;
+use crate::globals::{DEPTH, HEIGHT, MIN_HEIGHT, WIDTH};
+use crate::resources::settings::{BoidSettings, CollisionSettings, ForceCombination, GroupsTargets, Integrator, OrientationSettings};
+use crate::resources::terrain::TerrainHeightmap;
+use crate::systems::collision::resolve_collision;
+use crate::systems::integration::{integrate, limit_turn, rk2_correct, Kinematics, SpeedLimits};
+use crate::steering::combination::combine_forces;
+use crate::steering::registry::{self, SteeringRegistry, BUILTIN_COUNT};
+
+// Nombre de boids traités par tâche rayon
+const CHUNK_SIZE: usize = 256;
+
+/// Paramètres figés pour une étape du noyau compact.
+/// Seules les règles intégrées du `SteeringRegistry` y sont évaluées ;
+/// `new` laisse `obstacles` vide, à remplir par l'appelant.
+pub struct FlockParams {
+    pub cohesion_range: f32,
+    pub alignment_range: f32,
+    pub separation_range: f32,
+    pub cohesion_coeff: f32,
+    pub alignment_coeff: f32,
+    pub separation_coeff: f32,
+    pub collision_coeff: f32,
+    pub attraction_coeff: f32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    // Vitesse de virage maximale (rad/s, 0 : illimitée)
+    pub max_turn_rate: f32,
+    pub cos_half_fov: f32,
+    pub max_force: f32,
+    pub force_combination: ForceCombination,
+    // Ordre de priorité restreint aux règles intégrées
+    pub priority_order: Vec<usize>,
+    pub bounce_against_walls: bool,
+    pub integrator: Integrator,
+    // Contrainte dure contre les obstacles, si activée
+    pub collision: Option<CollisionSettings>,
+    pub terrain: TerrainHeightmap,
+    pub targets: Vec<Vec3A>,
+    pub obstacles: Vec<(Vec3A, f32)>,
+}
+
+impl FlockParams {
+    pub fn new(
+        settings: &BoidSettings,
+        orientation_settings: &OrientationSettings,
+        groups_targets: &GroupsTargets,
+        steering_registry: &SteeringRegistry,
+        integrator: Integrator,
+        collision_settings: &CollisionSettings,
+        terrain: &TerrainHeightmap,
+    ) -> Self {
+        // Une règle désactivée revient à un coefficient nul
+        let coeff = |index: usize, value: f32| if steering_registry.is_enabled(index) { value } else { 0.0 };
+
+        FlockParams {
+            cohesion_range: settings.cohesion_range,
+            alignment_range: settings.alignment_range,
+            separation_range: settings.separation_range.max(settings.min_distance_between_boids),
+            cohesion_coeff: coeff(registry::COHESION, settings.cohesion_coeff),
+            alignment_coeff: coeff(registry::ALIGNMENT, settings.alignment_coeff),
+            separation_coeff: coeff(registry::SEPARATION, settings.separation_coeff),
+            collision_coeff: coeff(registry::OBSTACLE_AVOIDANCE, settings.collision_coeff),
+            attraction_coeff: coeff(registry::ATTRACTION, settings.attraction_coeff),
+            min_speed: settings.min_speed,
+            max_speed: settings.max_speed,
+            max_turn_rate: orientation_settings.max_turn_rate.to_radians(),
+            cos_half_fov: (settings.field_of_view / 2.0).to_radians().cos(),
+            max_force: settings.max_force,
+            force_combination: settings.force_combination,
+            priority_order: steering_registry
+                .priority_order()
+                .iter()
+                .copied()
+                .filter(|index| *index < BUILTIN_COUNT)
+                .collect(),
+            bounce_against_walls: settings.bounce_against_walls,
+            integrator,
+            collision: collision_settings.enabled.then_some(*collision_settings),
+            terrain: terrain.clone(),
+            targets: groups_targets.targets.iter().map(|target| Vec3A::from(*target)).collect(),
+            obstacles: Vec::new(),
+        }
+    }
+
+    fn speed_limits(&self) -> SpeedLimits {
+        SpeedLimits { min_speed: self.min_speed, max_speed: self.max_speed }
+    }
+
+    // Même borne de virage que `integrate_boids`
+    fn limit_turn(&self, previous: Vec3, next: Vec3, delta_secs: f32) -> Vec3 {
+        if self.max_turn_rate > 0.0 {
+            limit_turn(previous, next, self.max_turn_rate * delta_secs)
+        } else {
+            next
+        }
+    }
+}
+
+/// Grille uniforme triée par cellule, reconstruite à chaque étape.
+#[derive(Default)]
+struct SpatialGrid {
+    cell_size: f32,
+    origin: Vec3A,
+    dims: UVec3,
+    cell_start: Vec<u32>,
+    sorted: Vec<u32>,
+}
+
+impl SpatialGrid {
+    fn cell_coords(&self, position: Vec3A) -> UVec3 {
+        let local = ((position - self.origin) / self.cell_size).floor();
+        let max = self.dims.as_vec3a() - Vec3A::ONE;
+        local.clamp(Vec3A::ZERO, max).as_uvec3()
+    }
+
+    fn cell_index(&self, coords: UVec3) -> usize {
+        (coords.x + self.dims.x * (coords.y + self.dims.y * coords.z)) as usize
+    }
+
+    fn rebuild(&mut self, positions: &[Vec3A], cell_size: f32) {
+        // La zone de vol plus une marge pour les boids qui débordent
+        let padding = Vec3A::splat(cell_size);
+        let min = Vec3A::new(-WIDTH / 2.0, 0.0, -DEPTH / 2.0) - padding;
+        let max = Vec3A::new(WIDTH / 2.0, HEIGHT, DEPTH / 2.0) + padding;
+
+        self.cell_size = cell_size.max(1.0);
+        self.origin = min;
+        self.dims = ((max - min) / self.cell_size).ceil().as_uvec3().max(UVec3::ONE);
+
+        let cell_count = (self.dims.x * self.dims.y * self.dims.z) as usize;
+        let cells: Vec<usize> = positions
+            .par_iter()
+            .map(|position| self.cell_index(self.cell_coords(*position)))
+            .collect();
+
+        // Tri par comptage : cell_start[c]..cell_start[c + 1] indexe `sorted`
+        self.cell_start.clear();
+        self.cell_start.resize(cell_count + 1, 0);
+        for &cell in &cells {
+            self.cell_start[cell + 1] += 1;
+        }
+        for cell in 0..cell_count {
+            self.cell_start[cell + 1] += self.cell_start[cell];
+        }
+
+        let mut cursor = self.cell_start.clone();
+        self.sorted.clear();
+        self.sorted.resize(positions.len(), 0);
+        for (index, &cell) in cells.iter().enumerate() {
+            self.sorted[cursor[cell] as usize] = index as u32;
+            cursor[cell] += 1;
+        }
+    }
+
+    fn cell_range(&self, coords: UVec3) -> std::ops::Range<usize> {
+        let cell = self.cell_index(coords);
+        self.cell_start[cell] as usize..self.cell_start[cell + 1] as usize
+    }
+}
+
+/// Noyau de simulation compact : les boids sont rangés en tableaux contigus
+/// (structure de tableaux) plutôt que lus composant par composant.
+///
+/// Il ne simule que les cinq règles intégrées, le champ de vision conique de `BoidSettings`,
+/// l'intégrateur, les collisions dures et le confinement au-dessus du relief. Les règles
+/// ajoutées par plugin, les traits individuels, la perception, le perchage, la fatigue,
+/// les meneurs, la formation et l'effarouchement n'existent qu'en simulation ECS : leurs
+/// systèmes ne tournent pas et l'interface les grise avec ce backend.
+#[derive(Resource, Default)]
+pub struct FlockCore {
+    pub entities: Vec<Entity>,
+    pub positions: Vec<Vec3A>,
+    pub velocities: Vec<Vec3A>,
+    pub accelerations: Vec<Vec3A>,
+    // Détail par règle de `accelerations`, recopié sur les entités
+    pub steering: Vec<[Vec3A; BUILTIN_COUNT]>,
+    pub groups: Vec<u8>,
+    // Mémoire de l'intégrateur, voir `IntegratorState`
+    previous_accelerations: Vec<Vec3A>,
+    start_positions: Vec<Vec3A>,
+    start_velocities: Vec<Vec3A>,
+    start_accelerations: Vec<Vec3A>,
+    grid: SpatialGrid,
+}
+
+impl FlockCore {
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entities.clear();
+        self.positions.clear();
+        self.velocities.clear();
+        self.accelerations.clear();
+        self.steering.clear();
+        self.groups.clear();
+        self.previous_accelerations.clear();
+    }
+
+    pub fn push(&mut self, entity: Entity, position: Vec3, velocity: Vec3, group: u8) -> usize {
+        self.entities.push(entity);
+        self.positions.push(position.into());
+        self.velocities.push(velocity.into());
+        self.accelerations.push(Vec3A::ZERO);
+        self.steering.push([Vec3A::ZERO; BUILTIN_COUNT]);
+        self.groups.push(group);
+        self.previous_accelerations.push(Vec3A::ZERO);
+        self.entities.len() - 1
+    }
+
+    /// Avance d'un pas et renvoie le nombre de contacts résolus avec les obstacles.
+    pub fn step(&mut self, params: &FlockParams, delta_secs: f32) -> u32 {
+        self.grid.rebuild(&self.positions, params.cohesion_range);
+        self.compute_accelerations(params);
+
+        if params.integrator == Integrator::Rk2 {
+            // Prédiction, puis correction avec les forces réévaluées à l'état prédit
+            self.start_positions.clone_from(&self.positions);
+            self.start_velocities.clone_from(&self.velocities);
+            self.start_accelerations.clone_from(&self.accelerations);
+            self.integrate(params, Integrator::ExplicitEuler, delta_secs);
+
+            self.grid.rebuild(&self.positions, params.cohesion_range);
+            self.compute_accelerations(params);
+            self.correct(params, delta_secs);
+        } else {
+            self.integrate(params, params.integrator, delta_secs);
+        }
+
+        match &params.collision {
+            Some(collision_settings) => self.resolve_collisions(params, collision_settings),
+            None => 0,
+        }
+    }
+
+    fn compute_accelerations(&mut self, params: &FlockParams) {
+        let FlockCore { positions, velocities, accelerations, steering, groups, grid, .. } = self;
+        let (positions, velocities, groups, grid) = (&*positions, &*velocities, &*groups, &*grid);
+
+        accelerations
+            .par_chunks_mut(CHUNK_SIZE)
+            .zip(steering.par_chunks_mut(CHUNK_SIZE))
+            .enumerate()
+            .for_each(|(chunk_index, (accelerations, steering))| {
+                let first = chunk_index * CHUNK_SIZE;
+                for (offset, (acceleration, forces)) in accelerations.iter_mut().zip(steering.iter_mut()).enumerate() {
+                    let index = first + offset;
+                    *forces = flock_kernel(index, positions, velocities, groups[index], grid, params);
+                    *acceleration = combine_forces(forces, &params.priority_order, params.force_combination, params.max_force);
+                }
+            });
+    }
+
+    fn integrate(&mut self, params: &FlockParams, integrator: Integrator, delta_secs: f32) {
+        let FlockCore { positions, velocities, accelerations, previous_accelerations, .. } = self;
+        let predicting = integrator != params.integrator;
+
+        positions
+            .par_iter_mut()
+            .zip(velocities.par_iter_mut())
+            .zip(accelerations.par_iter().zip(previous_accelerations.par_iter_mut()))
+            .with_min_len(CHUNK_SIZE)
+            .for_each(|((position, velocity), (acceleration, previous_acceleration))| {
+                let current = Kinematics {
+                    position: (*position).into(),
+                    velocity: (*velocity).into(),
+                    acceleration: (*acceleration).into(),
+                };
+                let (next_position, next_velocity) =
+                    integrate(integrator, current, (*previous_acceleration).into(), params.speed_limits(), delta_secs);
+                let next_velocity = params.limit_turn((*velocity).into(), next_velocity, delta_secs);
+                *position = next_position.into();
+                *velocity = next_velocity.into();
+
+                // La phase de prédiction RK2 ne compte pas comme une étape
+                if !predicting {
+                    *previous_acceleration = *acceleration;
+                    if params.bounce_against_walls {
+                        *velocity += confinement_nudge(*position, &params.terrain);
+                    }
+                }
+            });
+    }
+
+    fn resolve_collisions(&mut self, params: &FlockParams, collision_settings: &CollisionSettings) -> u32 {
+        self.positions
+            .par_iter_mut()
+            .zip(self.velocities.par_iter_mut())
+            .with_min_len(CHUNK_SIZE)
+            .map(|(position, velocity)| {
+                let mut collisions = 0;
+                for (center, radius) in &params.obstacles {
+                    if let Some((next_position, next_velocity)) = resolve_collision(
+                        (*position).into(),
+                        (*velocity).into(),
+                        (*center).into(),
+                        *radius,
+                        collision_settings,
+                    ) {
+                        *position = next_position.into();
+                        *velocity = next_velocity.into();
+                        collisions += 1;
+                    }
+                }
+                collisions
+            })
+            .sum()
+    }
+
+    fn correct(&mut self, params: &FlockParams, delta_secs: f32) {
+        let FlockCore { positions, velocities, accelerations, start_positions, start_velocities, start_accelerations, .. } = self;
+
+        positions
+            .par_iter_mut()
+            .zip(velocities.par_iter_mut())
+            .zip(accelerations.par_iter())
+            .zip(start_positions.par_iter().zip(start_velocities.par_iter()).zip(start_accelerations.par_iter()))
+            .with_min_len(CHUNK_SIZE)
+            .for_each(|(((position, velocity), acceleration), ((start_position, start_velocity), start_acceleration))| {
+                let start = Kinematics {
+                    position: (*start_position).into(),
+                    velocity: (*start_velocity).into(),
+                    acceleration: (*start_acceleration).into(),
+                };
+                let predicted = Kinematics {
+                    position: (*position).into(),
+                    velocity: (*velocity).into(),
+                    acceleration: (*acceleration).into(),
+                };
+                let (next_position, next_velocity) = rk2_correct(start, predicted, params.speed_limits(), delta_secs);
+                let next_velocity = params.limit_turn((*start_velocity).into(), next_velocity, delta_secs);
+                *position = next_position.into();
+                *velocity = next_velocity.into();
+
+                if params.bounce_against_walls {
+                    *velocity += confinement_nudge(*position, &params.terrain);
+                }
+            });
+    }
+}
+
+fn flock_kernel(
+    index: usize,
+    positions: &[Vec3A],
+    velocities: &[Vec3A],
+    group: u8,
+    grid: &SpatialGrid,
+    params: &FlockParams,
+) -> [Vec3A; BUILTIN_COUNT] {
+    let position = positions[index];
+    let velocity = velocities[index];
+    let heading = velocity.normalize_or_zero();
+    let cohesion_range_squared = params.cohesion_range * params.cohesion_range;
+
+    let mut separation_sum = Vec3A::ZERO;
+    let mut alignment_sum = Vec3A::ZERO;
+    let mut alignment_count = 0u32;
+    let mut cohesion_sum = Vec3A::ZERO;
+    let mut cohesion_count = 0u32;
+
+    let center = grid.cell_coords(position).as_ivec3();
+    for dz in -1..=1 {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let coords = center + IVec3::new(dx, dy, dz);
+                if coords.cmplt(IVec3::ZERO).any() || coords.cmpge(grid.dims.as_ivec3()).any() {
+                    continue;
+                }
+
+                for &other in &grid.sorted[grid.cell_range(coords.as_uvec3())] {
+                    let other = other as usize;
+                    if other == index {
+                        continue;
+                    }
+
+                    let to_other = positions[other] - position;
+                    let distance_squared = to_other.length_squared();
+                    if distance_squared > cohesion_range_squared {
+                        continue;
+                    }
+
+                    // Même test de champ de vision que `is_in_field_of_view`, sans racine pour le cône
+                    let distance = distance_squared.sqrt();
+                    if heading != Vec3A::ZERO && distance > 0.0 && heading.dot(to_other) < params.cos_half_fov * distance {
+                        continue;
+                    }
+
+                    if distance < params.separation_range {
+                        if distance > 0.0 {
+                            separation_sum -= to_other / distance;
+                        }
+                    } else if distance < params.alignment_range {
+                        alignment_sum += velocities[other];
+                        alignment_count += 1;
+                    } else if distance < params.cohesion_range {
+                        cohesion_sum += positions[other];
+                        cohesion_count += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut forces = [Vec3A::ZERO; BUILTIN_COUNT];
+    forces[registry::SEPARATION] = separation_sum * params.separation_coeff;
+
+    if alignment_count > 0 {
+        forces[registry::ALIGNMENT] = (alignment_sum / alignment_count as f32 - velocity) * params.alignment_coeff;
+    }
+    if cohesion_count > 0 {
+        forces[registry::COHESION] = (cohesion_sum / cohesion_count as f32 - position) * params.cohesion_coeff;
+    }
+    if let Some(target) = params.targets.get(group as usize) {
+        forces[registry::ATTRACTION] = (*target - position) * params.attraction_coeff;
+    }
+
+    let avoidance_range = params.separation_range * 2.0;
+    let mut avoidance = Vec3A::ZERO;
+    for (obstacle_pos, radius) in &params.obstacles {
+        let away = position - *obstacle_pos;
+        let surface_distance = away.length() - radius;
+        if surface_distance < avoidance_range && surface_distance > 0.0 {
+            let strength = 1.0 - surface_distance / avoidance_range;
+            avoidance += away.normalize_or_zero() * strength * params.collision_coeff;
+        }
+    }
+    forces[registry::OBSTACLE_AVOIDANCE] = avoidance;
+
+    forces
+}
+
+// Équivalent de `confine_boids` pour le noyau compact
+fn confinement_nudge(position: Vec3A, terrain: &TerrainHeightmap) -> Vec3A {
+    let margin = 10.0;
+    let turn_factor = 10.0;
+    let mut nudge = Vec3A::ZERO;
+
+    if position.x < -WIDTH / 2.0 + margin {
+        nudge.x += turn_factor;
+    } else if position.x > WIDTH / 2.0 - margin {
+        nudge.x -= turn_factor;
+    }
+
+    let floor = terrain.height_at(position.x, position.z) + MIN_HEIGHT;
+    if position.y < floor + margin {
+        nudge.y += turn_factor;
+    } else if position.y > HEIGHT - margin {
+        nudge.y -= turn_factor;
+    }
+
+    if position.z < -DEPTH / 2.0 + margin {
+        nudge.z += turn_factor;
+    } else if position.z > DEPTH / 2.0 - margin {
+        nudge.z -= turn_factor;
+    }
+
+    nudge
+}
+
+// Début de l'étape ECS en cours, pour comparer les deux chemins
+#[derive(Resource, Default)]
+pub struct FlockStepTimer {
+    pub started: Option<std::time::Instant>,
+}
@@ -0,0 +1,20 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+
+/// Place attribuée à un boid dans la formation de son groupe.
+#[derive(Clone, Copy)]
+pub struct FormationSlot {
+    pub position: Vec3,
+    // Vitesse du repère de groupe, que la place suit
+    pub velocity: Vec3,
+}
+
+/// Places courantes et avancement du fondu entre formation et vol libre.
+#[derive(Resource, Default)]
+pub struct FormationState {
+    pub slots: HashMap<Entity, FormationSlot>,
+    // Indice de place de chaque boid, conservé d'une frame à l'autre
+    pub assignments: HashMap<Entity, usize>,
+    // 0 en vol libre, 1 en formation complète
+    pub blend: f32,
+}
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use crate::components::boid::{BoidTraits, FlightState, SteeringForces};
+use crate::steering::Neighbours;
+
+/// État d'un boid tel que vu par `flocking_system`, relu à chaque frame.
+pub struct BoidReport {
+    pub group: u8,
+    pub flight_state: FlightState,
+    // Réserve et aspiration, si le boid porte un modèle d'énergie
+    pub energy: Option<(f32, bool)>,
+    pub traits: Option<BoidTraits>,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub forces: SteeringForces,
+    pub neighbours: Neighbours,
+}
+
+/// Boid choisi au clic dans la vue.
+#[derive(Resource, Default)]
+pub struct SelectedBoid {
+    pub entity: Option<Entity>,
+    pub report: Option<BoidReport>,
+    // Voisins tels que perçus par `flocking_system` au dernier pas, avec le boid concerné
+    pub perceived: Option<(Entity, Neighbours)>,
+}
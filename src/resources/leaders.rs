@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+/// Meneurs en place, groupe par groupe.
+#[derive(Resource, Default)]
+pub struct GroupLeaders {
+    pub groups: Vec<Vec<Entity>>,
+    // Instant du prochain relais de mandat
+    pub next_rotation: f32,
+}
+
+impl GroupLeaders {
+    pub fn is_leader(&self, entity: Entity) -> bool {
+        self.groups.iter().any(|leaders| leaders.contains(&entity))
+    }
+}
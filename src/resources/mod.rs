@@ -1 +1,13 @@
-pub mod settings;
\ No newline at end of file
+pub mod camera;
+pub mod collision;
+pub mod energy;
+pub mod flock_core;
+pub mod formation;
+pub mod inspection;
+pub mod leaders;
+pub mod perch;
+pub mod rendering;
+pub mod settings;
+pub mod startle;
+pub mod terrain;
+pub mod wind;
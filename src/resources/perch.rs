@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use crate::globals::{DEPTH, HEIGHT, WIDTH};
+
+/// Segments sur lesquels les boids peuvent se poser, comme des fils électriques.
+#[derive(Resource)]
+pub struct PerchLines {
+    pub lines: Vec<(Vec3, Vec3)>,
+}
+
+impl Default for PerchLines {
+    fn default() -> Self {
+        let height = HEIGHT * 0.5;
+        PerchLines {
+            lines: vec![
+                (Vec3::new(-WIDTH / 2.0, height, -DEPTH / 4.0), Vec3::new(WIDTH / 2.0, height, -DEPTH / 4.0)),
+                (Vec3::new(-WIDTH / 2.0, height + 2.0, DEPTH / 4.0), Vec3::new(WIDTH / 2.0, height + 2.0, DEPTH / 4.0)),
+            ],
+        }
+    }
+}
+
+impl PerchLines {
+    /// Point le plus proche de `position` sur l'ensemble des segments.
+    pub fn closest_point(&self, position: Vec3) -> Option<Vec3> {
+        self.lines
+            .iter()
+            .map(|(start, end)| {
+                let segment = *end - *start;
+                let along = ((position - *start).dot(segment) / segment.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+                *start + segment * along
+            })
+            .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)))
+    }
+}
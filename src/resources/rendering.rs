@@ -0,0 +1,89 @@
+use bevy::gltf::Gltf;
+use bevy::prelude::*;
+
+// Couleur de chaque groupe en rendu instancié
+pub const GROUP_COLORS: [Color; 2] = [
+    Color::srgb(0.9, 0.35, 0.15),
+    Color::srgb(0.15, 0.45, 0.95),
+];
+
+// Oiseau découpé en corps + deux ailes pour l'animation procédurale
+pub struct ArticulatedBird {
+    pub body: Handle<Mesh>,
+    // Maillage de chaque aile (côté +1 puis -1), recentré sur son articulation
+    pub wings: [(Handle<Mesh>, Vec3); 2],
+    // Axe longitudinal du modèle, autour duquel les ailes pivotent
+    pub hinge_axis: Vec3,
+}
+
+#[derive(Resource)]
+pub struct BirdAssets {
+    pub gltf: Handle<Gltf>,
+    pub scene: Handle<Scene>,
+    // Fusion de toutes les primitives du glTF, disponible une fois le modèle chargé
+    pub instanced_mesh: Option<Handle<Mesh>>,
+    // Version simplifiée du maillage fusionné pour la distance intermédiaire
+    pub low_poly_mesh: Option<Handle<Mesh>>,
+    pub articulated: Option<ArticulatedBird>,
+    pub point_mesh: Handle<Mesh>,
+    pub group_materials: Vec<Handle<StandardMaterial>>,
+    pub point_materials: Vec<Handle<StandardMaterial>>,
+}
+
+impl FromWorld for BirdAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let gltf = asset_server.load("models/bird.gltf");
+        let scene = asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/bird.gltf"));
+
+        // Rayon exprimé dans le repère du boid, mis à l'échelle par `BoidSettings::size`
+        let point_mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Sphere::new(12.0).mesh().uv(6, 4));
+
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let group_materials = GROUP_COLORS
+            .iter()
+            .map(|color| {
+                materials.add(StandardMaterial {
+                    base_color: *color,
+                    perceptual_roughness: 0.8,
+                    double_sided: true,
+                    cull_mode: None,
+                    ..default()
+                })
+            })
+            .collect();
+        let point_materials = GROUP_COLORS
+            .iter()
+            .map(|color| {
+                materials.add(StandardMaterial {
+                    base_color: *color,
+                    unlit: true,
+                    ..default()
+                })
+            })
+            .collect();
+
+        BirdAssets {
+            gltf,
+            scene,
+            instanced_mesh: None,
+            low_poly_mesh: None,
+            articulated: None,
+            point_mesh,
+            group_materials,
+            point_materials,
+        }
+    }
+}
+
+impl BirdAssets {
+    pub fn group_material(&self, group: u8) -> Handle<StandardMaterial> {
+        self.group_materials[group as usize % self.group_materials.len()].clone()
+    }
+
+    pub fn point_material(&self, group: u8) -> Handle<StandardMaterial> {
+        self.point_materials[group as usize % self.point_materials.len()].clone()
+    }
+}
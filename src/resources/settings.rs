@@ -1,6 +1,36 @@
 use bevy::prelude::*;
 use crate::globals::{WIDTH, HEIGHT};
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SimulationBackend {
+    // Un boid = une entité, forces lues via les requêtes ECS
+    Ecs,
+    // Noyau compact en tableaux contigus, recopié dans l'ECS pour le rendu
+    Packed,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoidRenderMode {
+    // Une scène glTF complète par boid
+    Scene,
+    // Un seul maillage partagé, dessiné en instances par groupe
+    Instanced,
+    // Corps et ailes séparés, battement procédural
+    Animated,
+    // Modèle animé, maillage simplifié ou point selon la distance à la caméra
+    Lod,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ForceCombination {
+    // Somme pondérée de toutes les règles, sans plafond
+    WeightedSum,
+    // Accumulation par priorité : les règles urgentes consomment le budget `max_force` en premier
+    Prioritized,
+    // Somme pondérée puis tronquée à `max_force`
+    WeightedTruncated,
+}
+
 #[derive(Resource)]
 pub struct BoidSettings {
     pub count: usize,
@@ -16,9 +46,13 @@ pub struct BoidSettings {
     pub collision_coeff: f32,
     pub min_speed: f32,
     pub max_speed: f32,
+    pub max_force: f32,
+    pub force_combination: ForceCombination,
     pub bounce_against_walls: bool,
     pub attraction_coeff: f32,
-    pub field_of_view: f32
+    pub field_of_view: f32,
+    pub simulation_backend: SimulationBackend,
+    pub render_mode: BoidRenderMode,
 }
 
 impl Default for BoidSettings {
@@ -37,9 +71,85 @@ impl Default for BoidSettings {
             collision_coeff: 24.0,
             min_speed: 20.0,
             max_speed: 80.0,
+            max_force: 1000.0,
+            force_combination: ForceCombination::WeightedSum,
             bounce_against_walls: true,
             attraction_coeff: 1.0,
-            field_of_view: 90.0
+            field_of_view: 90.0,
+            simulation_backend: SimulationBackend::Ecs,
+            render_mode: BoidRenderMode::Scene,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct LodSettings {
+    // En deçà : modèle détaillé
+    pub near_distance: f32,
+    // Au-delà : simple point
+    pub far_distance: f32,
+    // Largeur de la bande autour de chaque seuil où le niveau courant est conservé
+    pub hysteresis: f32,
+}
+
+impl Default for LodSettings {
+    fn default() -> Self {
+        LodSettings {
+            near_distance: 180.0,
+            far_distance: 260.0,
+            hysteresis: 20.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct WingSettings {
+    // Fréquence de battement à vitesse minimale (Hz)
+    pub flap_frequency: f32,
+    // Fréquence ajoutée à vitesse maximale (Hz)
+    pub speed_frequency_gain: f32,
+    // Fréquence ajoutée par unité d'accélération verticale (Hz)
+    pub climb_frequency_gain: f32,
+    // Amplitude du battement (°)
+    pub flap_amplitude: f32,
+    // Dièdre des ailes en vol plané (°)
+    pub glide_angle: f32,
+    // Effort (gain de vitesse + montée) sous lequel le boid plane
+    pub glide_threshold: f32,
+}
+
+impl Default for WingSettings {
+    fn default() -> Self {
+        WingSettings {
+            flap_frequency: 2.0,
+            speed_frequency_gain: 4.0,
+            climb_frequency_gain: 0.05,
+            flap_amplitude: 40.0,
+            glide_angle: 8.0,
+            glide_threshold: 5.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct OrientationSettings {
+    // Inclinaison par unité d'accélération latérale (0 : pas d'inclinaison)
+    pub banking_coeff: f32,
+    // Inclinaison maximale (°)
+    pub max_bank_angle: f32,
+    // Vitesse de convergence vers l'orientation visée (1/s, 0 : immédiat)
+    pub smoothing: f32,
+    // Vitesse de rotation maximale (°/s, 0 : illimitée)
+    pub max_turn_rate: f32,
+}
+
+impl Default for OrientationSettings {
+    fn default() -> Self {
+        OrientationSettings {
+            banking_coeff: 0.01,
+            max_bank_angle: 60.0,
+            smoothing: 8.0,
+            max_turn_rate: 360.0,
         }
     }
 }
@@ -58,4 +168,410 @@ impl Default for GroupsTargets {
             ]
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlayScope {
+    // Seulement le boid sélectionné dans l'inspecteur
+    #[default]
+    Selected,
+    All,
+}
+
+#[derive(Resource)]
+pub struct DebugOverlaySettings {
+    pub scope: OverlayScope,
+    pub velocity: bool,
+    pub forces: bool,
+    pub ranges: bool,
+    pub field_of_view: bool,
+    pub group_targets: bool,
+    pub obstacle_shells: bool,
+    pub wind_field: bool,
+    // Longueur affichée par unité de force ou de vitesse
+    pub vector_scale: f32,
+}
+
+impl Default for DebugOverlaySettings {
+    fn default() -> Self {
+        DebugOverlaySettings {
+            scope: OverlayScope::Selected,
+            velocity: false,
+            forces: false,
+            ranges: false,
+            field_of_view: false,
+            group_targets: false,
+            obstacle_shells: false,
+            wind_field: false,
+            vector_scale: 0.1,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct WanderSettings {
+    // Poids de la règle d'errance (0 : désactivée)
+    pub weight: f32,
+    // Vitesse de variation de la direction d'errance
+    pub rate: f32,
+}
+
+impl Default for WanderSettings {
+    fn default() -> Self {
+        WanderSettings {
+            weight: 0.0,
+            rate: 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Integrator {
+    // Position avec l'ancienne vitesse, puis vitesse
+    ExplicitEuler,
+    // Vitesse d'abord, puis position avec la nouvelle vitesse
+    SemiImplicitEuler,
+    // Verlet en vitesse, avec l'accélération de l'étape précédente
+    Verlet,
+    // Heun : prédiction d'Euler puis correction avec les forces réévaluées
+    Rk2,
+}
+
+#[derive(Resource, Clone, Copy)]
+pub struct IntegrationSettings {
+    pub integrator: Integrator,
+    // Au-delà de ce pas de temps, l'étape est découpée en sous-étapes
+    pub max_substep: f32,
+    pub max_substeps: u32,
+}
+
+impl Default for IntegrationSettings {
+    fn default() -> Self {
+        IntegrationSettings {
+            integrator: Integrator::SemiImplicitEuler,
+            max_substep: 1.0 / 60.0,
+            max_substeps: 8,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CollisionResponse {
+    // Composante normale inversée, atténuée par `restitution`
+    Reflect,
+    // Composante normale supprimée : le boid glisse le long de la surface
+    Slide,
+}
+
+#[derive(Resource, Clone, Copy)]
+pub struct CollisionSettings {
+    pub enabled: bool,
+    pub response: CollisionResponse,
+    pub restitution: f32,
+    // Rayon attribué à chaque boid pour le contact
+    pub boid_radius: f32,
+}
+
+impl Default for CollisionSettings {
+    fn default() -> Self {
+        CollisionSettings {
+            enabled: false,
+            response: CollisionResponse::Slide,
+            restitution: 0.5,
+            boid_radius: 0.5,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct TerrainSettings {
+    // Sans relief, le sol est le plan y = 0
+    pub enabled: bool,
+    pub seed: u32,
+    // Hauteur maximale du relief
+    pub amplitude: f32,
+    // Fréquence spatiale du bruit
+    pub scale: f32,
+    pub octaves: usize,
+    // Hauteur de vol visée au-dessus du sol local
+    pub altitude: f32,
+    pub altitude_coeff: f32,
+}
+
+impl Default for TerrainSettings {
+    fn default() -> Self {
+        TerrainSettings {
+            enabled: false,
+            seed: 0,
+            amplitude: 15.0,
+            scale: 0.02,
+            octaves: 4,
+            altitude: 30.0,
+            altitude_coeff: 2.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PerchSettings {
+    pub enabled: bool,
+    // Probabilité par seconde qu'un boid en vol décide de se poser
+    pub landing_rate: f32,
+    pub landing_speed: f32,
+    pub min_rest: f32,
+    pub max_rest: f32,
+    pub takeoff_duration: f32,
+    pub perch_on_terrain: bool,
+    pub perch_on_obstacles: bool,
+    pub perch_on_lines: bool,
+    // Un envol fait s'envoler les boids posés à proximité
+    pub startle_cascade: bool,
+    pub cascade_radius: f32,
+    // Délai maximal avant que le voisin ne réagisse
+    pub cascade_delay: f32,
+}
+
+impl Default for PerchSettings {
+    fn default() -> Self {
+        PerchSettings {
+            enabled: false,
+            landing_rate: 0.05,
+            landing_speed: 20.0,
+            min_rest: 3.0,
+            max_rest: 10.0,
+            takeoff_duration: 1.0,
+            perch_on_terrain: true,
+            perch_on_obstacles: true,
+            perch_on_lines: true,
+            startle_cascade: true,
+            cascade_radius: 15.0,
+            cascade_delay: 0.3,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct EnergySettings {
+    pub enabled: bool,
+    // Dépense par seconde à vitesse maximale
+    pub speed_drain: f32,
+    // Dépense par seconde et par unité d'accélération
+    pub acceleration_drain: f32,
+    pub glide_recovery: f32,
+    pub perched_recovery: f32,
+    // En deçà de cette accélération, un boid qui descend plane
+    pub glide_threshold: f32,
+    // Portée et économie de l'aspiration derrière un voisin
+    pub draft_range: f32,
+    pub draft_saving: f32,
+    // Fraction de `max_speed` accessible à énergie nulle
+    pub exhausted_speed_factor: f32,
+    // Attraction vers l'aspiration, multipliée par la fatigue
+    pub drafting_coeff: f32,
+    // Cohésion ajoutée à énergie nulle, proportionnelle à la fatigue
+    pub fatigue_cohesion_coeff: f32,
+}
+
+impl Default for EnergySettings {
+    fn default() -> Self {
+        EnergySettings {
+            enabled: false,
+            speed_drain: 0.02,
+            acceleration_drain: 0.0001,
+            glide_recovery: 0.05,
+            perched_recovery: 0.2,
+            glide_threshold: 20.0,
+            draft_range: 10.0,
+            draft_saving: 0.5,
+            exhausted_speed_factor: 0.5,
+            drafting_coeff: 5.0,
+            fatigue_cohesion_coeff: 10.0,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum LeaderSelection {
+    // Meneurs fixés, relayés dans l'ordre des boids du groupe
+    Designated,
+    // Boids les plus avancés vers la cible du groupe
+    FrontMost,
+}
+
+#[derive(Resource)]
+pub struct LeaderSettings {
+    pub enabled: bool,
+    pub selection: LeaderSelection,
+    pub leaders_per_group: usize,
+    // Durée d'un mandat en secondes ; 0 pour ne jamais relayer
+    pub rotation_period: f32,
+    // Attraction des meneurs vers la cible du groupe
+    pub target_coeff: f32,
+    // Alignement des suiveurs sur les meneurs visibles
+    pub follow_coeff: f32,
+    pub show_leaders: bool,
+}
+
+impl Default for LeaderSettings {
+    fn default() -> Self {
+        LeaderSettings {
+            enabled: false,
+            selection: LeaderSelection::FrontMost,
+            leaders_per_group: 1,
+            rotation_period: 20.0,
+            target_coeff: 3.0,
+            follow_coeff: 10.0,
+            show_leaders: true,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum FormationShape {
+    V,
+    Echelon,
+    LineAbreast,
+    SphereShell,
+    Grid,
+}
+
+#[derive(Resource)]
+pub struct FormationSettings {
+    pub enabled: bool,
+    pub shape: FormationShape,
+    // Distance entre deux places voisines
+    pub spacing: f32,
+    // En deçà de cette distance, le boid ralentit en approchant sa place
+    pub arrival_radius: f32,
+    pub formation_coeff: f32,
+    // Durée du fondu à l'activation et à la désactivation (s)
+    pub blend_time: f32,
+    pub show_slots: bool,
+}
+
+impl Default for FormationSettings {
+    fn default() -> Self {
+        FormationSettings {
+            enabled: false,
+            shape: FormationShape::V,
+            spacing: 6.0,
+            arrival_radius: 15.0,
+            formation_coeff: 10.0,
+            blend_time: 2.0,
+            show_slots: false,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct StartleSettings {
+    pub enabled: bool,
+    pub escape_duration: f32,
+    // Multiplicateur de `max_speed` pendant la fuite
+    pub escape_speed_factor: f32,
+    pub escape_coeff: f32,
+    // Temps de réaction d'un voisin, plus une part aléatoire
+    pub latency: f32,
+    pub latency_jitter: f32,
+    pub propagation_radius: f32,
+    // Période d'insensibilité après une fuite
+    pub refractory: f32,
+    // Déclenchements spontanés par seconde, sur tout le groupe
+    pub random_rate: f32,
+    // La caméra fait office de prédateur en deçà de ce rayon
+    pub camera_threat: bool,
+    pub camera_threat_radius: f32,
+    pub show_waves: bool,
+}
+
+impl Default for StartleSettings {
+    fn default() -> Self {
+        StartleSettings {
+            enabled: false,
+            escape_duration: 0.8,
+            escape_speed_factor: 1.8,
+            escape_coeff: 200.0,
+            latency: 0.15,
+            latency_jitter: 0.05,
+            propagation_radius: 10.0,
+            refractory: 3.0,
+            random_rate: 0.0,
+            camera_threat: false,
+            camera_threat_radius: 20.0,
+            show_waves: true,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum TraitDistribution {
+    // Tirage uniforme dans [1 - écart, 1 + écart]
+    Uniform,
+    // Loi normale centrée sur 1, d'écart-type `écart`
+    Normal,
+}
+
+#[derive(Resource)]
+pub struct TraitSettings {
+    pub enabled: bool,
+    pub distribution: TraitDistribution,
+    pub speed_spread: f32,
+    pub field_of_view_spread: f32,
+    pub range_spread: f32,
+    pub coeff_spread: f32,
+}
+
+impl Default for TraitSettings {
+    fn default() -> Self {
+        TraitSettings {
+            enabled: false,
+            distribution: TraitDistribution::Uniform,
+            speed_spread: 0.1,
+            field_of_view_spread: 0.1,
+            range_spread: 0.1,
+            coeff_spread: 0.2,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PerceptionSettings {
+    // Écart-type du bruit gaussien sur la position et la vélocité perçues
+    pub position_noise: f32,
+    pub velocity_noise: f32,
+    // Un voisin caché derrière un voisin plus proche n'est pas perçu
+    pub neighbour_occlusion: bool,
+    // Rayon apparent d'un boid pour l'occlusion
+    pub body_radius: f32,
+    // Nombre maximal de voisins perçus, les plus proches ; 0 pour illimité
+    pub max_neighbours: usize,
+    // Un obstacle entre le boid et son voisin bloque la vue (un test de rayon par voisin)
+    pub obstacle_occlusion: bool,
+    // Les voisins sont perçus tels qu'ils étaient il y a `reaction_delay` secondes
+    pub reaction_delay: f32,
+    // Champ vertical distinct ; `BoidSettings::field_of_view` devient alors le champ horizontal
+    pub separate_vertical_fov: bool,
+    pub vertical_field_of_view: f32,
+    // Angle mort à l'arrière (°)
+    pub blind_spot: f32,
+    // Largeur des transitions entre séparation, alignement et cohésion ; 0 pour des seuils nets
+    pub band_softness: f32,
+}
+
+impl Default for PerceptionSettings {
+    fn default() -> Self {
+        PerceptionSettings {
+            position_noise: 0.0,
+            velocity_noise: 0.0,
+            neighbour_occlusion: false,
+            body_radius: 1.0,
+            max_neighbours: 0,
+            obstacle_occlusion: false,
+            reaction_delay: 0.0,
+            separate_vertical_fov: false,
+            vertical_field_of_view: 120.0,
+            blind_spot: 0.0,
+            band_softness: 0.0,
+        }
+    }
+}
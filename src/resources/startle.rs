@@ -0,0 +1,63 @@
+use std::collections::{HashMap, VecDeque};
+use bevy::prelude::*;
+
+// Nombre de vagues conservées pour l'affichage
+pub const WAVE_HISTORY: usize = 8;
+
+/// Suivi d'une vague d'effarouchement depuis le boid déclencheur.
+pub struct StartleWave {
+    pub id: u32,
+    pub origin: Vec3,
+    pub started: f32,
+    pub reached: u32,
+    // Boid touché le plus loin de l'origine, et instant de sa réaction
+    pub front_distance: f32,
+    pub front_time: f32,
+}
+
+impl StartleWave {
+    /// Vitesse moyenne du front, en unités par seconde.
+    pub fn speed(&self) -> Option<f32> {
+        let elapsed = self.front_time - self.started;
+        (elapsed > 0.0).then(|| self.front_distance / elapsed)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct StartleWaves {
+    // Direction de fuite des boids en train de fuir
+    pub escaping: HashMap<Entity, Vec3>,
+    pub waves: VecDeque<StartleWave>,
+    pub next_wave: u32,
+}
+
+impl StartleWaves {
+    pub fn start_wave(&mut self, origin: Vec3, now: f32) -> u32 {
+        let id = self.next_wave;
+        self.next_wave += 1;
+        if self.waves.len() == WAVE_HISTORY {
+            self.waves.pop_front();
+        }
+        self.waves.push_back(StartleWave {
+            id,
+            origin,
+            started: now,
+            reached: 1,
+            front_distance: 0.0,
+            front_time: now,
+        });
+        id
+    }
+
+    pub fn record_reaction(&mut self, wave: u32, position: Vec3, now: f32) {
+        let Some(record) = self.waves.iter_mut().find(|record| record.id == wave) else {
+            return;
+        };
+        record.reached += 1;
+        let distance = record.origin.distance(position);
+        if distance > record.front_distance {
+            record.front_distance = distance;
+            record.front_time = now;
+        }
+    }
+}
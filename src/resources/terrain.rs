@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+use crate::globals::{DEPTH, WIDTH};
+use crate::resources::settings::TerrainSettings;
+
+// Nombre de cellules de la grille de hauteurs par côté
+pub const TERRAIN_RESOLUTION: usize = 64;
+
+/// Grille de hauteurs couvrant le sol de la zone de vol.
+#[derive(Resource, Clone, Default)]
+pub struct TerrainHeightmap {
+    // (TERRAIN_RESOLUTION + 1)² hauteurs, vides si le relief est désactivé
+    heights: Vec<f32>,
+    // Réglages ayant produit la grille : graine, amplitude, échelle, octaves
+    generated_from: Option<(u32, f32, f32, usize)>,
+}
+
+impl TerrainHeightmap {
+    pub fn is_up_to_date(&self, terrain_settings: &TerrainSettings) -> bool {
+        let key = terrain_settings.enabled.then_some((
+            terrain_settings.seed,
+            terrain_settings.amplitude,
+            terrain_settings.scale,
+            terrain_settings.octaves,
+        ));
+        self.generated_from == key
+    }
+
+    pub fn same_source(&self, other: &TerrainHeightmap) -> bool {
+        self.generated_from == other.generated_from
+    }
+
+    pub fn generate(terrain_settings: &TerrainSettings) -> Self {
+        if !terrain_settings.enabled {
+            return TerrainHeightmap::default();
+        }
+
+        let noise = Fbm::<Perlin>::new(terrain_settings.seed)
+            .set_octaves(terrain_settings.octaves.max(1))
+            .set_frequency(terrain_settings.scale as f64);
+
+        let side = TERRAIN_RESOLUTION + 1;
+        let mut heights = Vec::with_capacity(side * side);
+        for z in 0..side {
+            for x in 0..side {
+                let position = Self::grid_position(x, z);
+                let value = noise.get([position.x as f64, position.y as f64]) as f32;
+                // Bruit dans [-1, 1] ramené dans [0, amplitude]
+                heights.push(((value * 0.5 + 0.5).clamp(0.0, 1.0)) * terrain_settings.amplitude);
+            }
+        }
+
+        TerrainHeightmap {
+            heights,
+            generated_from: Some((
+                terrain_settings.seed,
+                terrain_settings.amplitude,
+                terrain_settings.scale,
+                terrain_settings.octaves,
+            )),
+        }
+    }
+
+    // Coordonnées (x, z) du sommet (x, z) de la grille
+    pub fn grid_position(x: usize, z: usize) -> Vec2 {
+        let cell = Vec2::new(WIDTH, DEPTH) / TERRAIN_RESOLUTION as f32;
+        Vec2::new(-WIDTH / 2.0, -DEPTH / 2.0) + Vec2::new(x as f32, z as f32) * cell
+    }
+
+    pub fn vertex_height(&self, x: usize, z: usize) -> f32 {
+        self.heights.get(z * (TERRAIN_RESOLUTION + 1) + x).copied().unwrap_or(0.0)
+    }
+
+    /// Hauteur du sol sous (x, z), interpolée ; bornée aux bords de la zone.
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        if self.heights.is_empty() {
+            return 0.0;
+        }
+
+        let cell = Vec2::new(WIDTH, DEPTH) / TERRAIN_RESOLUTION as f32;
+        let local = ((Vec2::new(x, z) + Vec2::new(WIDTH / 2.0, DEPTH / 2.0)) / cell)
+            .clamp(Vec2::ZERO, Vec2::splat(TERRAIN_RESOLUTION as f32));
+        let base = local.floor().min(Vec2::splat(TERRAIN_RESOLUTION as f32 - 1.0));
+        let fraction = local - base;
+        let (x0, z0) = (base.x as usize, base.y as usize);
+
+        let near = self.vertex_height(x0, z0).lerp(self.vertex_height(x0 + 1, z0), fraction.x);
+        let far = self.vertex_height(x0, z0 + 1).lerp(self.vertex_height(x0 + 1, z0 + 1), fraction.x);
+        near.lerp(far, fraction.y)
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use noise::{NoiseFn, Perlin};
+use crate::globals::HEIGHT;
+
+/// Vent appliqué à tout le volume de vol : vent uniforme avec rafales,
+/// tourbillon vertical et turbulence en bruit de curl.
+#[derive(Resource, Clone)]
+pub struct WindField {
+    pub enabled: bool,
+    pub uniform: Vec3,
+    // Rafales : modulation sinusoïdale du vent uniforme (0 : constant)
+    pub gust_amplitude: f32,
+    pub gust_frequency: f32,
+    // Tourbillon d'axe vertical, profil de Rankine (0 : désactivé)
+    pub vortex_strength: f32,
+    pub vortex_center: Vec3,
+    pub vortex_radius: f32,
+    // Turbulence sans divergence, évoluant dans le temps (0 : désactivée)
+    pub turbulence_strength: f32,
+    pub turbulence_scale: f32,
+    pub turbulence_speed: f32,
+    pub seed: u32,
+}
+
+impl Default for WindField {
+    fn default() -> Self {
+        WindField {
+            enabled: false,
+            uniform: Vec3::new(10.0, 0.0, 0.0),
+            gust_amplitude: 0.3,
+            gust_frequency: 0.2,
+            vortex_strength: 0.0,
+            vortex_center: Vec3::new(0.0, HEIGHT / 2.0, 0.0),
+            vortex_radius: 20.0,
+            turbulence_strength: 0.0,
+            turbulence_scale: 0.03,
+            turbulence_speed: 0.2,
+            seed: 0,
+        }
+    }
+}
+
+impl WindField {
+    pub fn sampler(&self) -> WindSampler {
+        WindSampler {
+            field: self.clone(),
+            potentials: [
+                Perlin::new(self.seed),
+                Perlin::new(self.seed.wrapping_add(1)),
+                Perlin::new(self.seed.wrapping_add(2)),
+            ],
+        }
+    }
+}
+
+/// Copie figée du champ et de ses bruits, à évaluer en n'importe quel point.
+pub struct WindSampler {
+    field: WindField,
+    potentials: [Perlin; 3],
+}
+
+impl WindSampler {
+    pub fn sample(&self, position: Vec3, elapsed_secs: f32) -> Vec3 {
+        let field = &self.field;
+        if !field.enabled {
+            return Vec3::ZERO;
+        }
+
+        let gust = 1.0 + field.gust_amplitude * (elapsed_secs * field.gust_frequency * std::f32::consts::TAU).sin();
+        let mut wind = field.uniform * gust;
+
+        if field.vortex_strength != 0.0 && field.vortex_radius > 0.0 {
+            let offset = Vec3::new(position.x - field.vortex_center.x, 0.0, position.z - field.vortex_center.z);
+            let distance = offset.length();
+            if distance > 0.0 {
+                let speed = if distance < field.vortex_radius {
+                    field.vortex_strength * distance / field.vortex_radius
+                } else {
+                    field.vortex_strength * field.vortex_radius / distance
+                };
+                wind += Vec3::Y.cross(offset / distance) * speed;
+            }
+        }
+
+        if field.turbulence_strength != 0.0 {
+            wind += self.curl(position * field.turbulence_scale, elapsed_secs * field.turbulence_speed)
+                * field.turbulence_strength;
+        }
+
+        wind
+    }
+
+    fn potential(&self, point: Vec3, time: f32) -> Vec3 {
+        let coords = [point.x as f64, point.y as f64, point.z as f64, time as f64];
+        Vec3::new(
+            self.potentials[0].get(coords) as f32,
+            self.potentials[1].get(coords) as f32,
+            self.potentials[2].get(coords) as f32,
+        )
+    }
+
+    // Rotationnel du potentiel par différences centrées
+    fn curl(&self, point: Vec3, time: f32) -> Vec3 {
+        let epsilon = 0.01;
+        let dx = (self.potential(point + Vec3::X * epsilon, time) - self.potential(point - Vec3::X * epsilon, time)) / (2.0 * epsilon);
+        let dy = (self.potential(point + Vec3::Y * epsilon, time) - self.potential(point - Vec3::Y * epsilon, time)) / (2.0 * epsilon);
+        let dz = (self.potential(point + Vec3::Z * epsilon, time) - self.potential(point - Vec3::Z * epsilon, time)) / (2.0 * epsilon);
+
+        Vec3::new(dy.z - dz.y, dz.x - dx.z, dx.y - dy.x)
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use crate::resources::settings::TerrainSettings;
+use crate::resources::terrain::TerrainHeightmap;
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+// Amortissement de la vitesse verticale, pour ne pas osciller autour de l'altitude visée
+const VERTICAL_DAMPING: f32 = 0.5;
+
+/// Maintient le boid à une hauteur donnée au-dessus du sol local.
+#[derive(Default)]
+pub struct AltitudeKeeping {
+    heightmap: TerrainHeightmap,
+    altitude: f32,
+    weight: f32,
+}
+
+impl SteeringBehaviour for AltitudeKeeping {
+    fn name(&self) -> &'static str {
+        "Maintien d'altitude"
+    }
+
+    fn color(&self) -> Color {
+        Color::srgb(0.55, 0.45, 0.3)
+    }
+
+    fn priority(&self) -> u32 {
+        15
+    }
+
+    fn weight(&self, _context: &SteeringContext) -> f32 {
+        self.weight
+    }
+
+    fn steer(&self, boid: &BoidState, _neighbours: &Neighbours, _context: &SteeringContext) -> Vec3 {
+        let ground = self.heightmap.height_at(boid.position.x, boid.position.z);
+        let error = ground + self.altitude - boid.position.y;
+        Vec3::Y * (error - boid.velocity.y * VERTICAL_DAMPING)
+    }
+
+    fn update(&mut self, world: &World) {
+        if let Some(terrain_settings) = world.get_resource::<TerrainSettings>() {
+            self.altitude = terrain_settings.altitude;
+            // Sans relief, le vol reste celui du modèle de base
+            self.weight = if terrain_settings.enabled { terrain_settings.altitude_coeff } else { 0.0 };
+        }
+        if let Some(heightmap) = world.get_resource::<TerrainHeightmap>()
+            && !heightmap.same_source(&self.heightmap)
+        {
+            self.heightmap = heightmap.clone();
+        }
+    }
+}
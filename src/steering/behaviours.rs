@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+pub const COHESION_COLOR: Color = Color::srgb(0.2, 0.7, 1.0);
+pub const SEPARATION_COLOR: Color = Color::srgb(1.0, 0.25, 0.2);
+pub const ALIGNMENT_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+pub const ATTRACTION_COLOR: Color = Color::srgb(0.3, 0.9, 0.3);
+pub const OBSTACLE_COLOR: Color = Color::srgb(0.9, 0.3, 0.9);
+
+/// Rapproche le boid du centre de ses voisins lointains.
+pub struct Cohesion;
+
+impl SteeringBehaviour for Cohesion {
+    fn name(&self) -> &'static str {
+        "Cohésion"
+    }
+
+    fn priority(&self) -> u32 {
+        30
+    }
+
+    fn color(&self) -> Color {
+        COHESION_COLOR
+    }
+
+    fn weight(&self, context: &SteeringContext) -> f32 {
+        context.settings.cohesion_coeff
+    }
+
+    fn steer(&self, boid: &BoidState, neighbours: &Neighbours, _context: &SteeringContext) -> Vec3 {
+        let total_weight: f32 = neighbours.cohesion.iter().map(|(_, _, weight)| *weight).sum();
+        if total_weight <= 0.0 {
+            return Vec3::ZERO;
+        }
+
+        let center = neighbours.cohesion.iter().map(|(_, position, weight)| *position * *weight).sum::<Vec3>()
+            / total_weight;
+        (center - boid.position) * boid.traits.cohesion
+    }
+}
+
+/// Éloigne le boid des voisins trop proches.
+pub struct Separation;
+
+impl SteeringBehaviour for Separation {
+    fn name(&self) -> &'static str {
+        "Séparation"
+    }
+
+    fn priority(&self) -> u32 {
+        10
+    }
+
+    fn color(&self) -> Color {
+        SEPARATION_COLOR
+    }
+
+    fn weight(&self, context: &SteeringContext) -> f32 {
+        context.settings.separation_coeff
+    }
+
+    fn steer(&self, boid: &BoidState, neighbours: &Neighbours, _context: &SteeringContext) -> Vec3 {
+        let mut separation_force = Vec3::ZERO;
+
+        for (_, other_pos, distance, weight) in &neighbours.separation {
+            if *distance > 0.0 {
+                separation_force += (boid.position - *other_pos) / *distance * *weight;
+            }
+        }
+
+        separation_force * boid.traits.separation
+    }
+}
+
+/// Aligne la vélocité du boid sur celle de ses voisins.
+pub struct Alignment;
+
+impl SteeringBehaviour for Alignment {
+    fn name(&self) -> &'static str {
+        "Alignement"
+    }
+
+    fn priority(&self) -> u32 {
+        20
+    }
+
+    fn color(&self) -> Color {
+        ALIGNMENT_COLOR
+    }
+
+    fn weight(&self, context: &SteeringContext) -> f32 {
+        context.settings.alignment_coeff
+    }
+
+    fn steer(&self, boid: &BoidState, neighbours: &Neighbours, _context: &SteeringContext) -> Vec3 {
+        let total_weight: f32 = neighbours.alignment.iter().map(|(_, _, _, weight)| *weight).sum();
+        if total_weight <= 0.0 {
+            return Vec3::ZERO;
+        }
+
+        let avg_velocity = neighbours.alignment.iter().map(|(_, _, velocity, weight)| *velocity * *weight).sum::<Vec3>()
+            / total_weight;
+        (avg_velocity - boid.velocity) * boid.traits.alignment
+    }
+}
+
+/// Attire le boid vers la cible de son groupe.
+pub struct TargetAttraction;
+
+impl SteeringBehaviour for TargetAttraction {
+    fn name(&self) -> &'static str {
+        "Attraction cible"
+    }
+
+    fn priority(&self) -> u32 {
+        40
+    }
+
+    fn color(&self) -> Color {
+        ATTRACTION_COLOR
+    }
+
+    fn weight(&self, context: &SteeringContext) -> f32 {
+        context.settings.attraction_coeff
+    }
+
+    fn steer(&self, boid: &BoidState, _neighbours: &Neighbours, context: &SteeringContext) -> Vec3 {
+        context
+            .targets
+            .get(boid.group as usize)
+            .map_or(Vec3::ZERO, |target| *target - boid.position)
+    }
+}
+
+/// Repousse le boid des obstacles dont il approche la surface.
+pub struct ObstacleAvoidance;
+
+impl SteeringBehaviour for ObstacleAvoidance {
+    fn name(&self) -> &'static str {
+        "Évitement obstacles"
+    }
+
+    fn priority(&self) -> u32 {
+        0
+    }
+
+    fn color(&self) -> Color {
+        OBSTACLE_COLOR
+    }
+
+    fn weight(&self, context: &SteeringContext) -> f32 {
+        context.settings.collision_coeff
+    }
+
+    fn steer(&self, boid: &BoidState, _neighbours: &Neighbours, context: &SteeringContext) -> Vec3 {
+        let avoidance_range = context.settings.separation_range * 2.0;
+        let mut avoidance_force = Vec3::ZERO;
+
+        for (obstacle_pos, radius) in context.obstacles {
+            // Distance depuis la surface de l'obstacle
+            let surface_distance = boid.position.distance(*obstacle_pos) - radius;
+
+            if surface_distance < avoidance_range && surface_distance > 0.0 {
+                // Force de répulsion inversement proportionnelle à la distance
+                let repulsion_dir = (boid.position - *obstacle_pos).normalize_or_zero();
+                let strength = 1.0 - surface_distance / avoidance_range;
+                avoidance_force += repulsion_dir * strength;
+            }
+        }
+
+        avoidance_force
+    }
+}
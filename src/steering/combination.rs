@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use crate::resources::settings::ForceCombination;
+
+/// Combine les forces des règles en une seule accélération.
+/// `priority_order` liste les indices de `forces` de la règle la plus urgente à la moins urgente.
+pub fn combine_forces<V>(forces: &[V], priority_order: &[usize], combination: ForceCombination, max_force: f32) -> V
+where
+    V: Copy + Default + std::ops::Add<Output = V> + std::ops::Mul<f32, Output = V> + VectorLength,
+{
+    match combination {
+        ForceCombination::WeightedSum => sum(forces),
+        ForceCombination::WeightedTruncated => truncate(sum(forces), max_force),
+        ForceCombination::Prioritized => {
+            let mut accumulated = V::default();
+            let mut remaining = max_force;
+
+            for force in priority_order.iter().filter_map(|index| forces.get(*index)) {
+                if remaining <= 0.0 {
+                    break;
+                }
+
+                // La dernière règle servie ne reçoit que ce qui reste du budget
+                let length = force.length();
+                if length <= remaining {
+                    accumulated = accumulated + *force;
+                    remaining -= length;
+                } else {
+                    accumulated = accumulated + *force * (remaining / length);
+                    remaining = 0.0;
+                }
+            }
+
+            accumulated
+        }
+    }
+}
+
+fn sum<V: Copy + Default + std::ops::Add<Output = V>>(forces: &[V]) -> V {
+    forces.iter().fold(V::default(), |total, force| total + *force)
+}
+
+fn truncate<V: Copy + std::ops::Mul<f32, Output = V> + VectorLength>(force: V, max_force: f32) -> V {
+    let length = force.length();
+    if length > max_force && length > 0.0 {
+        force * (max_force / length)
+    } else {
+        force
+    }
+}
+
+// Partagé par `Vec3` (ECS) et `Vec3A` (noyau compact)
+pub trait VectorLength {
+    fn length(&self) -> f32;
+}
+
+impl VectorLength for Vec3 {
+    fn length(&self) -> f32 {
+        Vec3::length(*self)
+    }
+}
+
+impl VectorLength for bevy::math::Vec3A {
+    fn length(&self) -> f32 {
+        bevy::math::Vec3A::length(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORCES: [Vec3; 3] = [Vec3::new(3.0, 0.0, 0.0), Vec3::new(0.0, 4.0, 0.0), Vec3::new(0.0, 0.0, 10.0)];
+
+    #[test]
+    fn weighted_sum_ignores_max_force() {
+        let combined = combine_forces(&FORCES, &[0, 1, 2], ForceCombination::WeightedSum, 1.0);
+        assert_eq!(combined, Vec3::new(3.0, 4.0, 10.0));
+    }
+
+    #[test]
+    fn weighted_truncated_clamps_the_sum() {
+        let combined = combine_forces(&FORCES[..2], &[0, 1], ForceCombination::WeightedTruncated, 2.5);
+        assert!((combined - Vec3::new(1.5, 2.0, 0.0)).length() < 1e-5);
+
+        let small = combine_forces(&FORCES[..2], &[0, 1], ForceCombination::WeightedTruncated, 10.0);
+        assert_eq!(small, Vec3::new(3.0, 4.0, 0.0));
+    }
+
+    #[test]
+    fn prioritized_serves_rules_in_priority_order() {
+        // La règle 2 passe en premier et consomme tout le budget
+        let combined = combine_forces(&FORCES, &[2, 0, 1], ForceCombination::Prioritized, 10.0);
+        assert_eq!(combined, Vec3::new(0.0, 0.0, 10.0));
+
+        // La dernière règle servie ne reçoit que le reste du budget
+        let combined = combine_forces(&FORCES, &[0, 1, 2], ForceCombination::Prioritized, 9.0);
+        assert!((combined - Vec3::new(3.0, 4.0, 2.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn prioritized_skips_unknown_indices() {
+        let combined = combine_forces(&FORCES, &[7, 0], ForceCombination::Prioritized, 100.0);
+        assert_eq!(combined, Vec3::new(3.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use crate::resources::settings::EnergySettings;
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+/// Décalage latéral de la place d'aspiration, en fraction de la portée
+const DRAFT_LATERAL: f32 = 0.5;
+
+/// Un boid fatigué cherche la place en retrait et de côté du voisin qui le précède.
+#[derive(Default)]
+pub struct Drafting {
+    weight: f32,
+    range: f32,
+}
+
+/// Voisin aligné le plus proche situé devant le boid, dans la portée d'aspiration.
+pub fn draft_leader(position: Vec3, velocity: Vec3, others: impl Iterator<Item = (Vec3, Vec3)>, range: f32) -> Option<(Vec3, Vec3)> {
+    let forward = velocity.normalize_or_zero();
+    others
+        .filter(|(other_pos, other_velocity)| {
+            let offset = *other_pos - position;
+            offset.dot(forward) > 0.0 && offset.length_squared() < range * range && other_velocity.dot(velocity) > 0.0
+        })
+        .min_by(|(a, _), (b, _)| a.distance_squared(position).total_cmp(&b.distance_squared(position)))
+}
+
+impl SteeringBehaviour for Drafting {
+    fn name(&self) -> &'static str {
+        "Aspiration"
+    }
+
+    fn color(&self) -> Color {
+        Color::srgb(0.9, 0.5, 0.8)
+    }
+
+    fn priority(&self) -> u32 {
+        50
+    }
+
+    fn weight(&self, _context: &SteeringContext) -> f32 {
+        self.weight
+    }
+
+    fn steer(&self, boid: &BoidState, neighbours: &Neighbours, _context: &SteeringContext) -> Vec3 {
+        let fatigue = 1.0 - boid.energy;
+        if fatigue <= 0.0 {
+            return Vec3::ZERO;
+        }
+
+        // La portée d'aspiration tombe d'ordinaire dans la bande de séparation : toutes les bandes comptent
+        let leaders = neighbours.perceived.iter().map(|(_, position, velocity, _)| (*position, *velocity));
+        let Some((leader_pos, leader_velocity)) = draft_leader(boid.position, boid.velocity, leaders, self.range) else {
+            return Vec3::ZERO;
+        };
+
+        // Place en retrait du meneur, du côté où se trouve déjà le boid
+        let leader_forward = leader_velocity.normalize_or_zero();
+        let offset = boid.position - leader_pos;
+        let lateral = (offset - leader_forward * offset.dot(leader_forward)).normalize_or(leader_forward.any_orthonormal_vector());
+        let slot = leader_pos + (lateral - leader_forward) * self.range * DRAFT_LATERAL;
+
+        (slot - boid.position).normalize_or_zero() * fatigue
+    }
+
+    fn update(&mut self, world: &World) {
+        if let Some(settings) = world.get_resource::<EnergySettings>() {
+            self.weight = if settings.enabled { settings.drafting_coeff } else { 0.0 };
+            self.range = settings.draft_range;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::settings::BoidSettings;
+
+    #[test]
+    fn draft_leader_picks_nearest_neighbour_ahead() {
+        let others = [
+            (Vec3::new(0.0, 0.0, 8.0), Vec3::Z),
+            (Vec3::new(1.0, 0.0, 4.0), Vec3::Z),
+            (Vec3::new(0.0, 0.0, 20.0), Vec3::Z),
+        ];
+        let leader = draft_leader(Vec3::ZERO, Vec3::Z, others.into_iter(), 10.0);
+        assert_eq!(leader, Some((Vec3::new(1.0, 0.0, 4.0), Vec3::Z)));
+    }
+
+    #[test]
+    fn draft_leader_ignores_neighbours_behind_out_of_range_or_opposite() {
+        let others = [
+            // Derrière
+            (Vec3::new(0.0, 0.0, -3.0), Vec3::Z),
+            // Hors de portée
+            (Vec3::new(0.0, 0.0, 12.0), Vec3::Z),
+            // Devant mais en sens inverse
+            (Vec3::new(0.0, 0.0, 3.0), -Vec3::Z),
+        ];
+        assert_eq!(draft_leader(Vec3::ZERO, Vec3::Z, others.into_iter(), 10.0), None);
+    }
+
+    #[test]
+    fn drafting_finds_leader_inside_separation_band() {
+        // Avec les réglages par défaut, la portée d'aspiration (10) est sous la portée de séparation (20)
+        let energy_settings = EnergySettings::default();
+        let drafting = Drafting { weight: energy_settings.drafting_coeff, range: energy_settings.draft_range };
+        let boid = BoidState {
+            entity: Entity::PLACEHOLDER,
+            position: Vec3::ZERO,
+            velocity: Vec3::Z * 20.0,
+            heading: Vec3::Z,
+            group: 0,
+            energy: 0.2,
+            traits: Default::default(),
+        };
+        let leader = (Entity::PLACEHOLDER, Vec3::new(2.0, 0.0, 6.0), Vec3::Z * 20.0, 6.3);
+        let neighbours = Neighbours {
+            separation: vec![(leader.0, leader.1, leader.3, 1.0)],
+            perceived: vec![leader],
+            ..default()
+        };
+        let settings = BoidSettings::default();
+        let context = SteeringContext { settings: &settings, targets: &[], obstacles: &[], elapsed_secs: 0.0 };
+
+        assert_ne!(drafting.steer(&boid, &neighbours, &context), Vec3::ZERO);
+    }
+}
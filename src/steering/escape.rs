@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+use crate::resources::settings::StartleSettings;
+use crate::resources::startle::StartleWaves;
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+/// Pousse les boids effarouchés dans leur direction de fuite.
+#[derive(Default)]
+pub struct Escape {
+    escaping: HashMap<Entity, Vec3>,
+    weight: f32,
+}
+
+impl SteeringBehaviour for Escape {
+    fn name(&self) -> &'static str {
+        "Fuite"
+    }
+
+    fn color(&self) -> Color {
+        Color::srgb(1.0, 0.4, 0.0)
+    }
+
+    fn priority(&self) -> u32 {
+        5
+    }
+
+    fn weight(&self, _context: &SteeringContext) -> f32 {
+        self.weight
+    }
+
+    fn steer(&self, boid: &BoidState, _neighbours: &Neighbours, _context: &SteeringContext) -> Vec3 {
+        self.escaping.get(&boid.entity).copied().unwrap_or(Vec3::ZERO)
+    }
+
+    fn update(&mut self, world: &World) {
+        let (Some(settings), Some(waves)) = (world.get_resource::<StartleSettings>(), world.get_resource::<StartleWaves>()) else {
+            return;
+        };
+        self.weight = if settings.enabled { settings.escape_coeff } else { 0.0 };
+        self.escaping.clone_from(&waves.escaping);
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use crate::resources::settings::EnergySettings;
+use crate::steering::behaviours::Cohesion;
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+/// Un boid fatigué se rapproche davantage du groupe : cohésion supplémentaire proportionnelle à la fatigue.
+#[derive(Default)]
+pub struct FatigueCohesion {
+    weight: f32,
+}
+
+impl SteeringBehaviour for FatigueCohesion {
+    fn name(&self) -> &'static str {
+        "Regroupement des fatigués"
+    }
+
+    fn color(&self) -> Color {
+        Color::srgb(0.55, 0.6, 0.9)
+    }
+
+    fn priority(&self) -> u32 {
+        35
+    }
+
+    fn weight(&self, _context: &SteeringContext) -> f32 {
+        self.weight
+    }
+
+    fn steer(&self, boid: &BoidState, neighbours: &Neighbours, context: &SteeringContext) -> Vec3 {
+        let fatigue = 1.0 - boid.energy;
+        if fatigue <= 0.0 {
+            return Vec3::ZERO;
+        }
+
+        Cohesion.steer(boid, neighbours, context) * fatigue
+    }
+
+    fn update(&mut self, world: &World) {
+        if let Some(settings) = world.get_resource::<EnergySettings>() {
+            self.weight = if settings.enabled { settings.fatigue_cohesion_coeff } else { 0.0 };
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use bevy::prelude::*;
+use crate::resources::formation::{FormationSlot, FormationState};
+use crate::resources::settings::FormationSettings;
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+/// Amène chaque boid sur sa place de formation avec un comportement d'arrivée.
+#[derive(Default)]
+pub struct Formation {
+    slots: HashMap<Entity, FormationSlot>,
+    weight: f32,
+    arrival_radius: f32,
+}
+
+impl SteeringBehaviour for Formation {
+    fn name(&self) -> &'static str {
+        "Formation"
+    }
+
+    fn color(&self) -> Color {
+        Color::srgb(0.4, 0.5, 1.0)
+    }
+
+    fn priority(&self) -> u32 {
+        35
+    }
+
+    fn weight(&self, _context: &SteeringContext) -> f32 {
+        self.weight
+    }
+
+    fn steer(&self, boid: &BoidState, _neighbours: &Neighbours, context: &SteeringContext) -> Vec3 {
+        let Some(slot) = self.slots.get(&boid.entity) else {
+            return Vec3::ZERO;
+        };
+
+        // Vitesse d'approche décroissante dans le rayon d'arrivée, en plus du mouvement de la place
+        let to_slot = slot.position - boid.position;
+        let ramp = (to_slot.length() / self.arrival_radius.max(f32::EPSILON)).min(1.0);
+        let desired = slot.velocity + to_slot.normalize_or_zero() * context.settings.max_speed * ramp;
+        desired - boid.velocity
+    }
+
+    fn update(&mut self, world: &World) {
+        let (Some(settings), Some(state)) = (world.get_resource::<FormationSettings>(), world.get_resource::<FormationState>()) else {
+            return;
+        };
+        self.weight = settings.formation_coeff * state.blend;
+        self.arrival_radius = settings.arrival_radius;
+        self.slots.clone_from(&state.slots);
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::resources::leaders::GroupLeaders;
+use crate::resources::settings::LeaderSettings;
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+/// Les meneurs tirent vers la cible du groupe, les suiveurs s'alignent sur les meneurs qu'ils voient.
+#[derive(Default)]
+pub struct Leadership {
+    enabled: bool,
+    leaders: HashSet<Entity>,
+    target_coeff: f32,
+    follow_coeff: f32,
+}
+
+impl SteeringBehaviour for Leadership {
+    fn name(&self) -> &'static str {
+        "Meneurs"
+    }
+
+    fn color(&self) -> Color {
+        Color::srgb(1.0, 0.6, 0.1)
+    }
+
+    fn priority(&self) -> u32 {
+        25
+    }
+
+    fn weight(&self, _context: &SteeringContext) -> f32 {
+        if self.enabled { 1.0 } else { 0.0 }
+    }
+
+    fn steer(&self, boid: &BoidState, neighbours: &Neighbours, context: &SteeringContext) -> Vec3 {
+        if self.leaders.contains(&boid.entity) {
+            return context
+                .targets
+                .get(boid.group as usize)
+                .map_or(Vec3::ZERO, |target| (*target - boid.position) * self.target_coeff);
+        }
+
+        // Moyenne pondérée par le poids de bande, comme `Alignment`
+        let (sum, total_weight) = neighbours
+            .alignment
+            .iter()
+            .filter(|(entity, _, _, _)| self.leaders.contains(entity))
+            .fold((Vec3::ZERO, 0.0), |(sum, total), (_, _, velocity, weight)| (sum + *velocity * *weight, total + *weight));
+        if total_weight <= 0.0 {
+            return Vec3::ZERO;
+        }
+
+        (sum / total_weight - boid.velocity) * self.follow_coeff
+    }
+
+    fn update(&mut self, world: &World) {
+        let Some(settings) = world.get_resource::<LeaderSettings>() else {
+            return;
+        };
+        self.enabled = settings.enabled;
+        self.target_coeff = settings.target_coeff;
+        self.follow_coeff = settings.follow_coeff;
+
+        self.leaders.clear();
+        if let Some(group_leaders) = world.get_resource::<GroupLeaders>() {
+            self.leaders.extend(group_leaders.groups.iter().flatten());
+        }
+    }
+}
@@ -0,0 +1,92 @@
+pub mod altitude;
+pub mod behaviours;
+pub mod combination;
+pub mod drafting;
+pub mod escape;
+pub mod fatigue;
+pub mod formation;
+pub mod leadership;
+pub mod registry;
+pub mod wander;
+pub mod wind;
+
+use bevy::prelude::*;
+use crate::components::boid::BoidTraits;
+use crate::resources::settings::BoidSettings;
+
+/// Voisins visibles d'un boid, classés selon la règle qu'ils alimentent.
+#[derive(Default, Clone)]
+pub struct Neighbours {
+    /// Entité, position, distance et poids des voisins trop proches
+    pub separation: Vec<(Entity, Vec3, f32, f32)>,
+    /// Entité, position, vélocité et poids des voisins à aligner
+    pub alignment: Vec<(Entity, Vec3, Vec3, f32)>,
+    /// Entité, position et poids des voisins à rejoindre
+    pub cohesion: Vec<(Entity, Vec3, f32)>,
+    /// Entité, position, vélocité et distance de tous les voisins perçus, toutes bandes confondues,
+    /// du plus proche au plus lointain
+    pub perceived: Vec<(Entity, Vec3, Vec3, f32)>,
+}
+
+impl Neighbours {
+    /// Nombre de voisins distincts, un voisin à cheval sur deux bandes n'étant compté qu'une fois.
+    pub fn in_view(&self) -> usize {
+        let mut entities: Vec<Entity> = self
+            .separation
+            .iter()
+            .map(|(entity, _, _, _)| *entity)
+            .chain(self.alignment.iter().map(|(entity, _, _, _)| *entity))
+            .chain(self.cohesion.iter().map(|(entity, _, _)| *entity))
+            .collect();
+        entities.sort_unstable();
+        entities.dedup();
+        entities.len()
+    }
+}
+
+/// État propre du boid évalué.
+pub struct BoidState {
+    pub entity: Entity,
+    pub position: Vec3,
+    pub velocity: Vec3,
+    // Direction du corps d'après le `Transform`, qui garde le dernier cap quand la vitesse est nulle
+    pub heading: Vec3,
+    pub group: u8,
+    // Réserve d'énergie dans [0, 1] ; 1 pour un boid sans modèle de fatigue
+    pub energy: f32,
+    pub traits: BoidTraits,
+}
+
+/// Données du monde partagées par tous les boids pendant une étape.
+pub struct SteeringContext<'a> {
+    pub settings: &'a BoidSettings,
+    pub targets: &'a [Vec3],
+    /// Centre et rayon de chaque obstacle
+    pub obstacles: &'a [(Vec3, f32)],
+    pub elapsed_secs: f32,
+}
+
+/// Règle de pilotage : transforme l'état d'un boid et de son voisinage en force.
+///
+/// `steer` renvoie une force non pondérée, multipliée ensuite par `weight`.
+pub trait SteeringBehaviour: Send + Sync + 'static {
+    /// Nom affiché dans l'interface
+    fn name(&self) -> &'static str;
+
+    /// Couleur des superpositions de débogage
+    fn color(&self) -> Color {
+        Color::srgb(0.7, 0.7, 0.7)
+    }
+
+    fn weight(&self, context: &SteeringContext) -> f32;
+
+    /// Rang dans l'accumulation par priorité, du plus urgent (0) au moins urgent
+    fn priority(&self) -> u32 {
+        100
+    }
+
+    fn steer(&self, boid: &BoidState, neighbours: &Neighbours, context: &SteeringContext) -> Vec3;
+
+    /// Appelé une fois par frame avant l'évaluation, pour lire réglages ou ressources
+    fn update(&mut self, _world: &World) {}
+}
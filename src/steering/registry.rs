@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+use crate::components::boid::SteeringForces;
+use crate::resources::settings::BoidSettings;
+use crate::steering::combination::combine_forces;
+use crate::steering::behaviours::{Alignment, Cohesion, ObstacleAvoidance, Separation, TargetAttraction};
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+// Indices des règles intégrées, toujours enregistrées en premier et dans cet ordre
+pub const COHESION: usize = 0;
+pub const SEPARATION: usize = 1;
+pub const ALIGNMENT: usize = 2;
+pub const ATTRACTION: usize = 3;
+pub const OBSTACLE_AVOIDANCE: usize = 4;
+pub const BUILTIN_COUNT: usize = 5;
+
+pub struct SteeringEntry {
+    pub behaviour: Box<dyn SteeringBehaviour>,
+    pub enabled: bool,
+}
+
+/// Règles évaluées pour chaque boid, dans l'ordre d'enregistrement.
+/// Les forces d'un boid (`SteeringForces`) sont rangées dans ce même ordre.
+#[derive(Resource)]
+pub struct SteeringRegistry {
+    entries: Vec<SteeringEntry>,
+    // Indices des règles triés par priorité croissante
+    priority_order: Vec<usize>,
+}
+
+impl Default for SteeringRegistry {
+    fn default() -> Self {
+        let mut registry = SteeringRegistry { entries: Vec::new(), priority_order: Vec::new() };
+        registry.register(Cohesion);
+        registry.register(Separation);
+        registry.register(Alignment);
+        registry.register(TargetAttraction);
+        registry.register(ObstacleAvoidance);
+        registry
+    }
+}
+
+impl SteeringRegistry {
+    pub fn register(&mut self, behaviour: impl SteeringBehaviour) -> usize {
+        self.entries.push(SteeringEntry {
+            behaviour: Box::new(behaviour),
+            enabled: true,
+        });
+
+        let entries = &self.entries;
+        self.priority_order = (0..entries.len()).collect();
+        self.priority_order.sort_by_key(|index| entries[*index].behaviour.priority());
+
+        self.entries.len() - 1
+    }
+
+    pub fn priority_order(&self) -> &[usize] {
+        &self.priority_order
+    }
+
+    pub fn entries_mut(&mut self) -> &mut [SteeringEntry] {
+        &mut self.entries
+    }
+
+    pub fn is_enabled(&self, index: usize) -> bool {
+        self.entries.get(index).is_some_and(|entry| entry.enabled)
+    }
+
+    /// Remplit `forces` avec la force pondérée de chaque règle.
+    pub fn evaluate(
+        &self,
+        boid: &BoidState,
+        neighbours: &Neighbours,
+        context: &SteeringContext,
+        forces: &mut SteeringForces,
+    ) {
+        forces.forces.clear();
+        forces.forces.extend(self.entries.iter().map(|entry| {
+            if entry.enabled {
+                entry.behaviour.steer(boid, neighbours, context) * entry.behaviour.weight(context)
+            } else {
+                Vec3::ZERO
+            }
+        }));
+    }
+
+    /// Accélération résultante selon la stratégie de combinaison choisie.
+    pub fn combine(&self, forces: &SteeringForces, boid_settings: &BoidSettings) -> Vec3 {
+        combine_forces(
+            &forces.forces,
+            &self.priority_order,
+            boid_settings.force_combination,
+            boid_settings.max_force,
+        )
+    }
+
+    /// Associe chaque force d'un boid à la règle qui l'a produite.
+    pub fn breakdown<'a>(
+        &'a self,
+        forces: &'a SteeringForces,
+    ) -> impl Iterator<Item = (&'a dyn SteeringBehaviour, Vec3)> + 'a {
+        self.entries
+            .iter()
+            .zip(forces.forces.iter())
+            .map(|(entry, force)| (entry.behaviour.as_ref(), *force))
+    }
+
+    // Règle dont la force est la plus intense
+    pub fn dominant(&self, forces: &SteeringForces) -> Option<&'static str> {
+        self.breakdown(forces)
+            .filter(|(_, force)| *force != Vec3::ZERO)
+            .max_by(|a, b| a.1.length_squared().total_cmp(&b.1.length_squared()))
+            .map(|(behaviour, _)| behaviour.name())
+    }
+}
+
+/// Permet à un plugin d'ajouter ses propres règles de pilotage.
+pub trait AddSteeringBehaviour {
+    fn add_steering_behaviour(&mut self, behaviour: impl SteeringBehaviour) -> &mut Self;
+}
+
+impl AddSteeringBehaviour for App {
+    fn add_steering_behaviour(&mut self, behaviour: impl SteeringBehaviour) -> &mut Self {
+        self.init_resource::<SteeringRegistry>();
+        self.world_mut().resource_mut::<SteeringRegistry>().register(behaviour);
+        self
+    }
+}
+
+pub fn update_steering_behaviours(world: &mut World) {
+    world.resource_scope(|world, mut registry: Mut<SteeringRegistry>| {
+        for entry in registry.entries_mut() {
+            entry.behaviour.update(world);
+        }
+    });
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use crate::resources::settings::WanderSettings;
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+/// Exemple de règle externe : dérive latérale pseudo-aléatoire, propre à chaque boid.
+#[derive(Default)]
+pub struct Wander {
+    weight: f32,
+    rate: f32,
+}
+
+impl SteeringBehaviour for Wander {
+    fn name(&self) -> &'static str {
+        "Errance"
+    }
+
+    fn color(&self) -> Color {
+        Color::srgb(0.6, 0.4, 0.2)
+    }
+
+    fn priority(&self) -> u32 {
+        90
+    }
+
+    fn weight(&self, _context: &SteeringContext) -> f32 {
+        self.weight
+    }
+
+    fn steer(&self, boid: &BoidState, _neighbours: &Neighbours, context: &SteeringContext) -> Vec3 {
+        // Phase propre au boid pour que les trajectoires ne soient pas synchronisées
+        let seed = boid.entity.index() as f32 * 1.618;
+        let t = context.elapsed_secs * self.rate + seed;
+        let wander = Vec3::new(
+            (t * 1.3).sin() + (t * 0.7 + seed).cos(),
+            (t * 0.9 + seed * 2.0).sin() * 0.5,
+            (t * 1.1).cos() + (t * 0.5 + seed).sin(),
+        );
+
+        // Ne garder que la composante latérale : l'errance tourne sans freiner
+        let forward = boid.velocity.normalize_or_zero();
+        (wander - forward * wander.dot(forward)).normalize_or_zero()
+    }
+
+    fn update(&mut self, world: &World) {
+        if let Some(settings) = world.get_resource::<WanderSettings>() {
+            self.weight = settings.weight;
+            self.rate = settings.rate;
+        }
+    }
+}
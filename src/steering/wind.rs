@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use crate::resources::wind::{WindField, WindSampler};
+use crate::steering::{BoidState, Neighbours, SteeringBehaviour, SteeringContext};
+
+/// Pousse les boids selon le `WindField`, échantillonné à leur position.
+#[derive(Default)]
+pub struct Wind {
+    sampler: Option<WindSampler>,
+}
+
+impl SteeringBehaviour for Wind {
+    fn name(&self) -> &'static str {
+        "Vent"
+    }
+
+    fn color(&self) -> Color {
+        Color::srgb(0.6, 0.9, 0.9)
+    }
+
+    fn priority(&self) -> u32 {
+        80
+    }
+
+    fn weight(&self, _context: &SteeringContext) -> f32 {
+        1.0
+    }
+
+    fn steer(&self, boid: &BoidState, _neighbours: &Neighbours, context: &SteeringContext) -> Vec3 {
+        self.sampler
+            .as_ref()
+            .map_or(Vec3::ZERO, |sampler| sampler.sample(boid.position, context.elapsed_secs))
+    }
+
+    fn update(&mut self, world: &World) {
+        self.sampler = world
+            .get_resource::<WindField>()
+            .filter(|field| field.enabled)
+            .map(WindField::sampler);
+    }
+}
@@ -1,16 +1,52 @@
-use crate::components::boid::{Boid, Obstacle, Velocity, Acceleration};
+use crate::components::boid::{Boid, BoidTraits, Energy, ExternalForce, FlightState, FlockSlot, Obstacle, Velocity, Acceleration, IntegratorState, PerceptionHistory, Startle, SteeringForces, WingFlap};
 use crate::globals::{DEPTH, HEIGHT, MIN_HEIGHT, WIDTH};
-use crate::resources::settings::BoidSettings;
+use crate::events::ResampleTraitsEvent;
+use crate::resources::settings::{BoidSettings, TraitDistribution, TraitSettings};
 use bevy::prelude::*;
 use rand::Rng;
 use std::f32::consts::PI;
 use crate::components::spatial::TrackedByKDTree3D;
 
-fn spawn_boid_entity(
-    commands: &mut Commands,
-    boid_settings: &BoidSettings,
-    asset_server: &Res<AssetServer>,
-) {
+const MIN_TRAIT_MULTIPLIER: f32 = 0.1;
+const MAX_TRAIT_MULTIPLIER: f32 = 3.0;
+
+// Tirage gaussien centré réduit (Box-Muller)
+pub fn standard_normal(rng: &mut impl Rng) -> f32 {
+    let u1: f32 = rng.random_range(f32::EPSILON..1.0);
+    let u2: f32 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+// Multiplicateur tiré autour de 1, borné pour rester positif
+fn sample_multiplier(rng: &mut impl Rng, distribution: TraitDistribution, spread: f32) -> f32 {
+    if spread <= 0.0 {
+        return 1.0;
+    }
+
+    let offset = match distribution {
+        TraitDistribution::Uniform => rng.random_range(-spread..=spread),
+        TraitDistribution::Normal => spread * standard_normal(rng),
+    };
+    (1.0 + offset).clamp(MIN_TRAIT_MULTIPLIER, MAX_TRAIT_MULTIPLIER)
+}
+
+fn sample_traits(rng: &mut impl Rng, trait_settings: &TraitSettings) -> BoidTraits {
+    if !trait_settings.enabled {
+        return BoidTraits::default();
+    }
+
+    let distribution = trait_settings.distribution;
+    BoidTraits {
+        speed: sample_multiplier(rng, distribution, trait_settings.speed_spread),
+        field_of_view: sample_multiplier(rng, distribution, trait_settings.field_of_view_spread),
+        range: sample_multiplier(rng, distribution, trait_settings.range_spread),
+        cohesion: sample_multiplier(rng, distribution, trait_settings.coeff_spread),
+        alignment: sample_multiplier(rng, distribution, trait_settings.coeff_spread),
+        separation: sample_multiplier(rng, distribution, trait_settings.coeff_spread),
+    }
+}
+
+fn spawn_boid_entity(commands: &mut Commands, boid_settings: &BoidSettings, trait_settings: &TraitSettings) {
     let mut rng = rand::rng();
     let group = rng.random_range(0..2);
 
@@ -33,8 +69,21 @@ fn spawn_boid_entity(
         Boid { group },
         Velocity { velocity: initial_velocity },
         Acceleration { acceleration: Vec3::ZERO },
+        ExternalForce::default(),
+        SteeringForces::default(),
+        IntegratorState::default(),
+        FlightState::Flying,
+        Energy { value: rng.random_range(0.8..=1.0), drafting: false },
+        Startle::Calm,
+        sample_traits(&mut rng, trait_settings),
+        PerceptionHistory::default(),
         TrackedByKDTree3D,
-        SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset("models/bird.gltf"))),
+        FlockSlot::default(),
+        WingFlap {
+            phase: rng.random_range(0.0..2.0 * PI),
+            amplitude: 0.0,
+            previous_velocity: initial_velocity,
+        },
         Transform {
             translation: random_pos,
             scale: Vec3::splat(boid_settings.size),
@@ -74,10 +123,50 @@ pub fn spawn_obstacles(
 
 pub fn spawn_boids(
     mut commands: Commands,
-    boid_settings: Res<BoidSettings>,
-    asset_server: Res<AssetServer>,
+    mut boid_settings: ResMut<BoidSettings>,
+    trait_settings: Res<TraitSettings>,
 ) {
     for _ in 0..boid_settings.count {
-        spawn_boid_entity(&mut commands, &boid_settings, &asset_server);
+        spawn_boid_entity(&mut commands, &boid_settings, &trait_settings);
+    }
+    boid_settings.previous_count = boid_settings.count;
+}
+
+pub fn adjust_boid_count(
+    mut commands: Commands,
+    mut boid_settings: ResMut<BoidSettings>,
+    trait_settings: Res<TraitSettings>,
+    boid_query: Query<Entity, With<Boid>>,
+) {
+    if boid_settings.count == boid_settings.previous_count {
+        return;
     }
-}
\ No newline at end of file
+
+    let current = boid_query.iter().len();
+    if boid_settings.count > current {
+        for _ in current..boid_settings.count {
+            spawn_boid_entity(&mut commands, &boid_settings, &trait_settings);
+        }
+    } else {
+        for entity in boid_query.iter().skip(boid_settings.count) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    boid_settings.previous_count = boid_settings.count;
+}
+
+pub fn resample_traits(
+    mut events: EventReader<ResampleTraitsEvent>,
+    mut boid_query: Query<&mut BoidTraits>,
+    trait_settings: Res<TraitSettings>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let mut rng = rand::rng();
+    for mut traits in boid_query.iter_mut() {
+        *traits = sample_traits(&mut rng, &trait_settings);
+    }
+}
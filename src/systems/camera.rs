@@ -0,0 +1,352 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit};
+use bevy::prelude::*;
+use bevy_egui::input::EguiWantsInput;
+use rand::Rng;
+use crate::components::boid::{Boid, Obstacle, Velocity};
+use crate::globals::{DEPTH, HEIGHT, WIDTH};
+use crate::resources::camera::{CameraMode, CameraSettings, CameraState, CinematicShot};
+use crate::resources::settings::GroupsTargets;
+use crate::systems::picking::{pick_boid, ray_sphere_distance, CursorView, PICK_RADIUS};
+
+// Délai maximal entre deux clics d'un double clic (s)
+const DOUBLE_CLICK_DELAY: f32 = 0.3;
+
+type CameraTransform<'w> = Single<'w, &'static mut Transform, (With<Camera3d>, Without<Boid>)>;
+
+/// Entrées souris des modes pilotés à la main, hors gestes captés par l'interface.
+#[derive(SystemParam)]
+pub struct MouseInput<'w> {
+    egui_wants_input: Res<'w, EguiWantsInput>,
+    buttons: Res<'w, ButtonInput<MouseButton>>,
+    motion: Res<'w, AccumulatedMouseMotion>,
+    scroll: Res<'w, AccumulatedMouseScroll>,
+}
+
+impl MouseInput<'_> {
+    fn captured_by_ui(&self) -> bool {
+        self.egui_wants_input.wants_any_pointer_input()
+    }
+}
+
+pub fn setup_camera(
+    mut commands: Commands,
+    camera_settings: Res<CameraSettings>,
+    mut camera_state: ResMut<CameraState>,
+) {
+    let target = camera_settings.orbit_target;
+    let mut transform = Transform::from_xyz(200.0, 200.0, 5.0).looking_at(target, Vec3::Y);
+    transform.translation = target - transform.forward() * camera_settings.orbit_distance;
+    camera_state.focus = target;
+
+    commands.spawn((
+        Name::new("Camera"),
+        Camera3d::default(),
+        transform,
+    ));
+}
+
+pub fn camera_mode_is(mode: CameraMode) -> impl Fn(Res<CameraSettings>) -> bool {
+    move |camera_settings: Res<CameraSettings>| camera_settings.mode == mode
+}
+
+pub fn camera_shortcuts(
+    keys: Res<ButtonInput<KeyCode>>,
+    egui_wants_input: Res<EguiWantsInput>,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut camera_state: ResMut<CameraState>,
+    groups_targets: Res<GroupsTargets>,
+    boid_query: Query<Entity, With<Boid>>,
+) {
+    if egui_wants_input.wants_any_keyboard_input() {
+        return;
+    }
+
+    // C : mode suivant
+    if keys.just_pressed(KeyCode::KeyC) {
+        let index = CameraMode::ALL.iter().position(|mode| *mode == camera_settings.mode).unwrap_or(0);
+        camera_settings.mode = CameraMode::ALL[(index + 1) % CameraMode::ALL.len()];
+        camera_state.shot_timer = Timer::from_seconds(0.0, TimerMode::Once);
+    }
+
+    // N : boid suivant
+    if keys.just_pressed(KeyCode::KeyN) {
+        camera_state.followed_boid = camera_state
+            .followed_boid
+            .and_then(|current| boid_query.iter().skip_while(|entity| *entity != current).nth(1))
+            .or_else(|| boid_query.iter().next());
+    }
+
+    // G : groupe suivant
+    if keys.just_pressed(KeyCode::KeyG) && !groups_targets.targets.is_empty() {
+        camera_settings.followed_group = (camera_settings.followed_group + 1) % groups_targets.targets.len() as u8;
+    }
+}
+
+fn rotate_from_mouse(rotation: Quat, delta: Vec2, camera_settings: &CameraSettings) -> Quat {
+    let (yaw, pitch, roll) = rotation.to_euler(EulerRot::YXZ);
+
+    let pitch = (pitch + delta.y * camera_settings.pitch_speed).clamp(
+        camera_settings.pitch_range.start,
+        camera_settings.pitch_range.end,
+    );
+    let yaw = yaw + delta.x * camera_settings.yaw_speed;
+    Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll)
+}
+
+fn zoom_from_scroll(camera_settings: &mut CameraSettings, mouse_scroll: &AccumulatedMouseScroll) {
+    let scroll = match mouse_scroll.unit {
+        MouseScrollUnit::Line => mouse_scroll.delta.y,
+        MouseScrollUnit::Pixel => mouse_scroll.delta.y / 40.0,
+    };
+    if scroll == 0.0 {
+        return;
+    }
+
+    let range = camera_settings.orbit_distance_range.clone();
+    camera_settings.orbit_distance = (camera_settings.orbit_distance * (-scroll * camera_settings.zoom_speed).exp())
+        .clamp(range.start, range.end);
+}
+
+fn smoothing_factor(camera_settings: &CameraSettings, delta_secs: f32) -> f32 {
+    1.0 - (-camera_settings.follow_smoothing * delta_secs).exp()
+}
+
+// Position de poursuite : derrière le boid et légèrement au-dessus
+fn chase_position(position: Vec3, velocity: Vec3, camera_settings: &CameraSettings) -> Vec3 {
+    let heading = velocity.try_normalize().unwrap_or(Vec3::X);
+    position - heading * camera_settings.follow_distance + Vec3::Y * camera_settings.follow_height
+}
+
+fn centroid<'a>(positions: impl Iterator<Item = &'a Transform>) -> Option<Vec3> {
+    let (sum, count) = positions.fold((Vec3::ZERO, 0), |(sum, count), transform| (sum + transform.translation, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+pub fn orbit(
+    mut camera: CameraTransform,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut camera_state: ResMut<CameraState>,
+    mouse: MouseInput,
+    time: Res<Time>,
+) {
+    if !mouse.captured_by_ui() {
+        zoom_from_scroll(&mut camera_settings, &mouse.scroll);
+
+        if mouse.buttons.pressed(MouseButton::Left) {
+            camera.rotation = rotate_from_mouse(camera.rotation, mouse.motion.delta, &camera_settings);
+        }
+
+        // Le point visé suit le curseur, proportionnellement à la distance
+        if mouse.buttons.pressed(MouseButton::Middle) {
+            let scale = camera_settings.pan_speed * camera_settings.orbit_distance;
+            let pan = (*camera.left() * mouse.motion.delta.x + *camera.up() * mouse.motion.delta.y) * scale;
+            camera_settings.orbit_target += pan;
+            camera_state.focus += pan;
+        }
+    }
+
+    let factor = smoothing_factor(&camera_settings, time.delta_secs());
+    camera_state.focus = camera_state.focus.lerp(camera_settings.orbit_target, factor);
+    camera.translation = camera_state.focus - camera.forward() * camera_settings.orbit_distance;
+}
+
+pub fn refocus_on_double_click(
+    mut camera_settings: ResMut<CameraSettings>,
+    mut camera_state: ResMut<CameraState>,
+    mouse: MouseInput,
+    cursor_view: CursorView,
+    boid_query: Query<(Entity, &Transform), With<Boid>>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Boid>>,
+    time: Res<Time>,
+) {
+    if mouse.captured_by_ui() || !mouse.buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let now = time.elapsed_secs();
+    let double_click = camera_state.last_click.is_some_and(|last| now - last < DOUBLE_CLICK_DELAY);
+    camera_state.last_click = if double_click { None } else { Some(now) };
+    if !double_click {
+        return;
+    }
+
+    let Some(ray) = cursor_view.ray() else {
+        return;
+    };
+
+    // Priorité au boid sous le curseur, puis aux obstacles, puis au sol
+    let boids = boid_query.iter().map(|(entity, transform)| (entity, transform.translation));
+    let target = pick_boid(ray, boids, PICK_RADIUS)
+        .map(|(_, position)| position)
+        .or_else(|| {
+            obstacle_query
+                .iter()
+                .filter_map(|(transform, obstacle)| ray_sphere_distance(ray, transform.translation, obstacle.radius))
+                .min_by(|a, b| a.total_cmp(b))
+                .map(|distance| ray.get_point(distance))
+        })
+        .or_else(|| {
+            ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Y))
+                .map(|distance| ray.get_point(distance))
+        });
+
+    if let Some(target) = target {
+        camera_settings.orbit_target = target;
+    }
+}
+
+pub fn free_fly(
+    mut camera: CameraTransform,
+    camera_settings: Res<CameraSettings>,
+    egui_wants_input: Res<EguiWantsInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    time: Res<Time>,
+) {
+    if mouse_buttons.pressed(MouseButton::Left) && !egui_wants_input.wants_any_pointer_input() {
+        camera.rotation = rotate_from_mouse(camera.rotation, mouse_motion.delta, &camera_settings);
+    }
+
+    if egui_wants_input.wants_any_keyboard_input() {
+        return;
+    }
+
+    // Touches physiques : ZQSD sur un clavier AZERTY
+    let mut direction = Vec3::ZERO;
+    for (key, axis) in [
+        (KeyCode::KeyW, *camera.forward()),
+        (KeyCode::KeyS, *camera.back()),
+        (KeyCode::KeyA, *camera.left()),
+        (KeyCode::KeyD, *camera.right()),
+        (KeyCode::KeyE, Vec3::Y),
+        (KeyCode::KeyQ, Vec3::NEG_Y),
+    ] {
+        if keys.pressed(key) {
+            direction += axis;
+        }
+    }
+
+    let boost = if keys.pressed(KeyCode::ShiftLeft) { camera_settings.fly_boost } else { 1.0 };
+    camera.translation += direction.normalize_or_zero() * camera_settings.fly_speed * boost * time.delta_secs();
+}
+
+pub fn follow_boid(
+    mut camera: CameraTransform,
+    camera_settings: Res<CameraSettings>,
+    mut camera_state: ResMut<CameraState>,
+    boid_query: Query<(Entity, &Transform, &Velocity), With<Boid>>,
+    time: Res<Time>,
+) {
+    // Le boid suivi a pu disparaître quand le nombre de boids change
+    let followed = camera_state
+        .followed_boid
+        .and_then(|entity| boid_query.get(entity).ok())
+        .or_else(|| boid_query.iter().next());
+    let Some((entity, transform, velocity)) = followed else {
+        return;
+    };
+    camera_state.followed_boid = Some(entity);
+
+    let desired = chase_position(transform.translation, velocity.velocity, &camera_settings);
+    let factor = smoothing_factor(&camera_settings, time.delta_secs());
+    camera.translation = camera.translation.lerp(desired, factor);
+    camera.look_at(transform.translation, Vec3::Y);
+}
+
+pub fn follow_group(
+    mut camera: CameraTransform,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut camera_state: ResMut<CameraState>,
+    mouse: MouseInput,
+    boid_query: Query<(&Boid, &Transform)>,
+    time: Res<Time>,
+) {
+    let group_positions = boid_query
+        .iter()
+        .filter(|(boid, _)| boid.group == camera_settings.followed_group)
+        .map(|(_, transform)| transform);
+    let Some(group_centroid) = centroid(group_positions) else {
+        return;
+    };
+
+    let factor = smoothing_factor(&camera_settings, time.delta_secs());
+    camera_state.focus = camera_state.focus.lerp(group_centroid, factor);
+
+    if !mouse.captured_by_ui() {
+        zoom_from_scroll(&mut camera_settings, &mouse.scroll);
+
+        if mouse.buttons.pressed(MouseButton::Left) {
+            camera.rotation = rotate_from_mouse(camera.rotation, mouse.motion.delta, &camera_settings);
+        }
+    }
+    camera.translation = camera_state.focus - camera.forward() * camera_settings.orbit_distance;
+}
+
+pub fn cinematic(
+    mut camera: CameraTransform,
+    camera_settings: Res<CameraSettings>,
+    mut camera_state: ResMut<CameraState>,
+    boid_query: Query<(Entity, &Transform, &Velocity), With<Boid>>,
+    time: Res<Time>,
+) {
+    let boid_count = boid_query.iter().len();
+    if boid_count == 0 {
+        return;
+    }
+    let flock_centroid = centroid(boid_query.iter().map(|(_, transform, _)| transform)).unwrap_or_default();
+
+    camera_state.shot_timer.tick(time.delta());
+    let cut = camera_state.shot_timer.finished()
+        || camera_state.followed_boid.is_none_or(|entity| boid_query.get(entity).is_err());
+
+    if cut {
+        let mut rng = rand::rng();
+        let shots = [CinematicShot::WideOrbit, CinematicShot::Chase, CinematicShot::FlyBy, CinematicShot::TopDown];
+        let next_shots: Vec<_> = shots.into_iter().filter(|shot| *shot != camera_state.shot).collect();
+        camera_state.shot = next_shots[rng.random_range(0..next_shots.len())];
+        camera_state.shot_timer = Timer::from_seconds(camera_settings.shot_duration, TimerMode::Once);
+        camera_state.shot_angle = rng.random_range(0.0..std::f32::consts::TAU);
+
+        let (subject, transform, velocity) = boid_query.iter().nth(rng.random_range(0..boid_count)).unwrap();
+        camera_state.followed_boid = Some(subject);
+
+        // Le point de passage est placé sur la trajectoire du boid, décalé sur le côté
+        let heading = velocity.velocity.try_normalize().unwrap_or(Vec3::X);
+        let side = heading.cross(Vec3::Y).normalize_or(Vec3::Z);
+        let anchor = transform.translation + heading * 40.0 + side * 15.0 + Vec3::Y * 5.0;
+        camera_state.shot_anchor = anchor.clamp(
+            Vec3::new(-WIDTH, 0.0, -DEPTH),
+            Vec3::new(WIDTH, HEIGHT * 1.5, DEPTH),
+        );
+    }
+
+    let Some((_, subject, subject_velocity)) = camera_state.followed_boid.and_then(|entity| boid_query.get(entity).ok()) else {
+        return;
+    };
+
+    match camera_state.shot {
+        CinematicShot::WideOrbit => {
+            camera_state.shot_angle += 0.15 * time.delta_secs();
+            let radius = camera_settings.orbit_distance * 0.7;
+            let angle = camera_state.shot_angle;
+            camera.translation = flock_centroid + Vec3::new(angle.cos() * radius, radius * 0.35, angle.sin() * radius);
+            camera.look_at(flock_centroid, Vec3::Y);
+        }
+        CinematicShot::Chase => {
+            let desired = chase_position(subject.translation, subject_velocity.velocity, &camera_settings);
+            let factor = if cut { 1.0 } else { smoothing_factor(&camera_settings, time.delta_secs()) };
+            camera.translation = camera.translation.lerp(desired, factor);
+            camera.look_at(subject.translation, Vec3::Y);
+        }
+        CinematicShot::FlyBy => {
+            camera.translation = camera_state.shot_anchor;
+            camera.look_at(subject.translation, Vec3::Y);
+        }
+        CinematicShot::TopDown => {
+            camera.translation = flock_centroid + Vec3::Y * camera_settings.orbit_distance * 0.6;
+            camera.look_at(flock_centroid, Vec3::NEG_Z);
+        }
+    }
+}
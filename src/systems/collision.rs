@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use crate::components::boid::{Boid, Obstacle, Velocity};
+use crate::resources::collision::CollisionStats;
+use crate::resources::settings::{CollisionResponse, CollisionSettings};
+
+/// Ramène un boid entré dans une sphère à sa surface et corrige sa vitesse.
+/// Renvoie `None` s'il n'y a pas de contact.
+pub fn resolve_collision(
+    position: Vec3,
+    velocity: Vec3,
+    center: Vec3,
+    radius: f32,
+    collision_settings: &CollisionSettings,
+) -> Option<(Vec3, Vec3)> {
+    let contact_radius = radius + collision_settings.boid_radius;
+    let offset = position - center;
+    if offset.length_squared() >= contact_radius * contact_radius {
+        return None;
+    }
+
+    // Un boid pile au centre est expulsé vers le haut
+    let normal = offset.try_normalize().unwrap_or(Vec3::Y);
+    let normal_speed = velocity.dot(normal);
+    let velocity = if normal_speed < 0.0 {
+        match collision_settings.response {
+            CollisionResponse::Reflect => velocity - normal * normal_speed * (1.0 + collision_settings.restitution),
+            CollisionResponse::Slide => velocity - normal * normal_speed,
+        }
+    } else {
+        velocity
+    };
+
+    Some((center + normal * contact_radius, velocity))
+}
+
+pub fn collisions_enabled(collision_settings: Res<CollisionSettings>) -> bool {
+    collision_settings.enabled
+}
+
+pub fn begin_collision_frame(mut collision_stats: ResMut<CollisionStats>) {
+    collision_stats.frame = 0;
+}
+
+pub fn resolve_obstacle_collisions(
+    mut boid_query: Query<(&mut Transform, &mut Velocity), With<Boid>>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Boid>>,
+    collision_settings: Res<CollisionSettings>,
+    mut collision_stats: ResMut<CollisionStats>,
+) {
+    let mut collisions = 0;
+
+    for (mut transform, mut velocity) in boid_query.iter_mut() {
+        for (obstacle_transform, obstacle) in obstacle_query.iter() {
+            if let Some((position, new_velocity)) = resolve_collision(
+                transform.translation,
+                velocity.velocity,
+                obstacle_transform.translation,
+                obstacle.radius,
+                &collision_settings,
+            ) {
+                transform.translation = position;
+                velocity.velocity = new_velocity;
+                collisions += 1;
+            }
+        }
+    }
+
+    collision_stats.record(collisions);
+}
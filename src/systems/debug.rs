@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+use crate::components::boid::{Boid, BoidTraits, Obstacle, SteeringForces, Velocity};
+use crate::resources::inspection::SelectedBoid;
+use crate::globals::{DEPTH, HEIGHT, WIDTH};
+use crate::resources::rendering::GROUP_COLORS;
+use crate::resources::wind::WindField;
+use crate::resources::settings::{BoidSettings, DebugOverlaySettings, GroupsTargets, OverlayScope, PerceptionSettings};
+use crate::steering::behaviours::{ALIGNMENT_COLOR, COHESION_COLOR, OBSTACLE_COLOR, SEPARATION_COLOR};
+use crate::steering::registry::SteeringRegistry;
+use crate::systems::flocking::{view_axes, FieldOfView};
+
+const VELOCITY_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const FOV_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.4);
+// Nombre de génératrices tracées pour le cône de vision
+const FOV_CONE_LINES: usize = 8;
+// Segments de chaque bord d'un champ de vision rectangulaire
+const FOV_EDGE_SEGMENTS: usize = 12;
+const BLIND_SPOT_COLOR: Color = Color::srgba(1.0, 0.3, 0.3, 0.4);
+const WIND_COLOR: Color = Color::srgb(0.6, 0.9, 0.9);
+// Points d'échantillonnage du vent par axe
+const WIND_GRID: UVec3 = UVec3::new(7, 4, 7);
+
+pub fn draw_boid_overlays(
+    mut gizmos: Gizmos,
+    overlay: Res<DebugOverlaySettings>,
+    selected: Res<SelectedBoid>,
+    boid_query: Query<(&Transform, &Velocity, &SteeringForces, Option<&BoidTraits>), With<Boid>>,
+    registry: Res<SteeringRegistry>,
+    boid_settings: Res<BoidSettings>,
+    perception: Res<PerceptionSettings>,
+) {
+    if !(overlay.velocity || overlay.forces || overlay.ranges || overlay.field_of_view) {
+        return;
+    }
+
+    let boids: Vec<_> = match overlay.scope {
+        OverlayScope::Selected => selected.entity.and_then(|entity| boid_query.get(entity).ok()).into_iter().collect(),
+        OverlayScope::All => boid_query.iter().collect(),
+    };
+
+    for (transform, velocity, steering, traits) in boids {
+        let position = transform.translation;
+        let traits = traits.copied().unwrap_or_default();
+
+        if overlay.velocity {
+            gizmos.arrow(position, position + velocity.velocity * overlay.vector_scale, VELOCITY_COLOR);
+        }
+
+        if overlay.forces {
+            for (behaviour, force) in registry.breakdown(steering) {
+                if force != Vec3::ZERO {
+                    gizmos.arrow(position, position + force * overlay.vector_scale, behaviour.color());
+                }
+            }
+        }
+
+        if overlay.ranges {
+            let separation_range = boid_settings.separation_range.max(boid_settings.min_distance_between_boids);
+            gizmos.sphere(position, separation_range * traits.range, SEPARATION_COLOR);
+            gizmos.sphere(position, boid_settings.alignment_range * traits.range, ALIGNMENT_COLOR);
+            gizmos.sphere(position, boid_settings.cohesion_range * traits.range, COHESION_COLOR);
+        }
+
+        if overlay.field_of_view {
+            let field_of_view = FieldOfView::new(&boid_settings, &perception, &traits);
+            draw_field_of_view(&mut gizmos, position, velocity.velocity, &field_of_view, boid_settings.cohesion_range * traits.range);
+        }
+    }
+}
+
+// Cône de demi-angle `half_angle` autour de `axis`, s'étendant jusqu'à `range`
+fn draw_cone(gizmos: &mut Gizmos, position: Vec3, axis: Vec3, half_angle: f32, range: f32, color: Color) {
+    let base_center = position + axis * range * half_angle.cos();
+    let base_radius = range * half_angle.sin();
+    let rotation = Quat::from_rotation_arc(Vec3::Z, axis);
+
+    gizmos.circle(Isometry3d::new(base_center, rotation), base_radius, color);
+    for i in 0..FOV_CONE_LINES {
+        let angle = i as f32 / FOV_CONE_LINES as f32 * std::f32::consts::TAU;
+        let rim = base_center + rotation * Vec3::new(angle.cos(), angle.sin(), 0.0) * base_radius;
+        gizmos.line(position, rim, color);
+    }
+}
+
+// Cône de vision, ou secteur délimité en azimut et en élévation, plus l'angle mort
+fn draw_field_of_view(gizmos: &mut Gizmos, position: Vec3, velocity: Vec3, field_of_view: &FieldOfView, range: f32) {
+    let Some(forward) = velocity.try_normalize() else {
+        return;
+    };
+
+    let half_horizontal = (field_of_view.horizontal / 2.0).to_radians();
+    match field_of_view.vertical {
+        None => draw_cone(gizmos, position, forward, half_horizontal, range, FOV_COLOR),
+        Some(vertical) => {
+            let half_vertical = (vertical / 2.0).to_radians();
+            let (right, up) = view_axes(forward);
+            let point = |azimuth: f32, elevation: f32| {
+                position + ((forward * azimuth.cos() + right * azimuth.sin()) * elevation.cos() + up * elevation.sin()) * range
+            };
+            let steps = |half: f32| (0..=FOV_EDGE_SEGMENTS).map(move |i| -half + 2.0 * half * i as f32 / FOV_EDGE_SEGMENTS as f32);
+
+            for elevation in [-half_vertical, half_vertical] {
+                gizmos.linestrip(steps(half_horizontal).map(|azimuth| point(azimuth, elevation)), FOV_COLOR);
+            }
+            for azimuth in [-half_horizontal, half_horizontal] {
+                gizmos.linestrip(steps(half_vertical).map(|elevation| point(azimuth, elevation)), FOV_COLOR);
+                for elevation in [-half_vertical, half_vertical] {
+                    gizmos.line(position, point(azimuth, elevation), FOV_COLOR);
+                }
+            }
+        }
+    }
+
+    if field_of_view.blind_spot > 0.0 {
+        let half_blind = (field_of_view.blind_spot / 2.0).to_radians();
+        draw_cone(gizmos, position, -forward, half_blind, range, BLIND_SPOT_COLOR);
+    }
+}
+
+pub fn draw_world_overlays(
+    mut gizmos: Gizmos,
+    overlay: Res<DebugOverlaySettings>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Boid>>,
+    boid_settings: Res<BoidSettings>,
+    groups_targets: Res<GroupsTargets>,
+    wind_field: Res<WindField>,
+    time: Res<Time>,
+) {
+    if overlay.wind_field && wind_field.enabled {
+        draw_wind_field(&mut gizmos, &wind_field, time.elapsed_secs());
+    }
+
+    if overlay.group_targets {
+        for (group, target) in groups_targets.targets.iter().enumerate() {
+            let color = GROUP_COLORS[group % GROUP_COLORS.len()];
+            gizmos.sphere(*target, 2.0, color);
+            gizmos.cross(*target, 4.0, color);
+        }
+    }
+
+    if overlay.obstacle_shells {
+        // Même portée que `ObstacleAvoidance`
+        let influence = boid_settings.separation_range * 2.0;
+        for (transform, obstacle) in obstacle_query.iter() {
+            gizmos.sphere(transform.translation, obstacle.radius + influence, OBSTACLE_COLOR);
+        }
+    }
+}
+
+// Grille de flèches couvrant le volume de vol, la plus longue occupant presque une cellule
+fn draw_wind_field(gizmos: &mut Gizmos, wind_field: &WindField, elapsed_secs: f32) {
+    let sampler = wind_field.sampler();
+    let min = Vec3::new(-WIDTH / 2.0, 0.0, -DEPTH / 2.0);
+    let cell = Vec3::new(WIDTH, HEIGHT, DEPTH) / WIND_GRID.as_vec3();
+
+    let mut samples = Vec::new();
+    for x in 0..WIND_GRID.x {
+        for y in 0..WIND_GRID.y {
+            for z in 0..WIND_GRID.z {
+                let position = min + (UVec3::new(x, y, z).as_vec3() + 0.5) * cell;
+                samples.push((position, sampler.sample(position, elapsed_secs)));
+            }
+        }
+    }
+
+    let strongest = samples.iter().map(|(_, wind)| wind.length()).fold(0.0, f32::max);
+    if strongest <= 0.0 {
+        return;
+    }
+
+    let scale = cell.min_element() * 0.8 / strongest;
+    for (position, wind) in samples {
+        gizmos.arrow(position, position + wind * scale, WIND_COLOR);
+    }
+}
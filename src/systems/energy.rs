@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use bevy_spatial::SpatialAccess;
+use crate::components::boid::{Boid, Energy, FlightState, IntegratorState, Velocity};
+use crate::components::spatial::NNTree3D;
+use crate::resources::energy::EnergyStats;
+use crate::resources::settings::{BoidSettings, EnergySettings};
+use crate::steering::drafting::draft_leader;
+
+// Seuil sous lequel un boid est compté comme épuisé
+pub const EXHAUSTED_THRESHOLD: f32 = 0.2;
+
+pub fn energy_enabled(energy_settings: Res<EnergySettings>) -> bool {
+    energy_settings.enabled
+}
+
+/// Vide la réserve selon l'effort fourni et la recharge en plané ou posé.
+pub fn update_energy(
+    boid_query: Query<(Entity, &Transform, &Velocity, &IntegratorState, &FlightState), With<Boid>>,
+    mut energy_query: Query<&mut Energy>,
+    kd_tree: Res<NNTree3D>,
+    energy_settings: Res<EnergySettings>,
+    boid_settings: Res<BoidSettings>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_secs();
+
+    for (entity, transform, velocity, state, flight_state) in boid_query.iter() {
+        let Ok(mut energy) = energy_query.get_mut(entity) else { continue; };
+
+        if let FlightState::Perched { .. } = flight_state {
+            energy.drafting = false;
+            energy.value = (energy.value + energy_settings.perched_recovery * delta_secs).min(1.0);
+            continue;
+        }
+
+        // Aspiration : un voisin en vol, devant et dans le même sens
+        let position = transform.translation;
+        let others = kd_tree
+            .within_distance(position, energy_settings.draft_range)
+            .into_iter()
+            .filter_map(|(_, other)| other.filter(|other| *other != entity))
+            .filter_map(|other| boid_query.get(other).ok())
+            .filter(|(_, _, _, _, other_state)| other_state.is_airborne())
+            .map(|(_, other_transform, other_velocity, _, _)| (other_transform.translation, other_velocity.velocity));
+        energy.drafting = draft_leader(position, velocity.velocity, others, energy_settings.draft_range).is_some();
+
+        let effort = state.previous_acceleration.length();
+        let speed_fraction = velocity.velocity.length() / boid_settings.max_speed.max(f32::EPSILON);
+        let mut drain = energy_settings.speed_drain * speed_fraction + energy_settings.acceleration_drain * effort;
+        if energy.drafting {
+            drain *= 1.0 - energy_settings.draft_saving;
+        }
+
+        let gliding = velocity.velocity.y <= 0.0 && effort < energy_settings.glide_threshold;
+        let recovery = if gliding { energy_settings.glide_recovery } else { 0.0 };
+
+        energy.value = (energy.value + (recovery - drain) * delta_secs).clamp(0.0, 1.0);
+    }
+}
+
+pub fn update_energy_stats(
+    query: Query<(&Boid, &Energy, &FlightState)>,
+    mut stats: ResMut<EnergyStats>,
+) {
+    let mut sum = 0.0;
+    let mut min = f32::MAX;
+    let mut exhausted = 0;
+    let mut drafting = 0;
+    let mut airborne = 0;
+    let mut groups: Vec<(f32, u32)> = Vec::new();
+
+    for (boid, energy, flight_state) in query.iter() {
+        sum += energy.value;
+        min = min.min(energy.value);
+        if energy.value < EXHAUSTED_THRESHOLD {
+            exhausted += 1;
+        }
+        if flight_state.is_airborne() {
+            airborne += 1;
+            if energy.drafting {
+                drafting += 1;
+            }
+        }
+
+        let group = boid.group as usize;
+        if groups.len() <= group {
+            groups.resize(group + 1, (0.0, 0));
+        }
+        groups[group].0 += energy.value;
+        groups[group].1 += 1;
+    }
+
+    let count = query.iter().len();
+    if count == 0 {
+        *stats = EnergyStats::default();
+        return;
+    }
+
+    stats.mean = sum / count as f32;
+    stats.min = min;
+    stats.exhausted = exhausted as f32 / count as f32;
+    stats.drafting = if airborne > 0 { drafting as f32 / airborne as f32 } else { 0.0 };
+    stats.group_means = groups
+        .into_iter()
+        .map(|(total, members)| if members > 0 { total / members as f32 } else { 0.0 })
+        .collect();
+}
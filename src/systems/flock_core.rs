@@ -0,0 +1,263 @@
+use bevy::diagnostic::{DiagnosticPath, Diagnostics};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::time::Instant;
+use crate::components::boid::{Boid, FlockSlot, Obstacle, SteeringForces, Velocity};
+use crate::resources::flock_core::{FlockCore, FlockParams, FlockStepTimer};
+use crate::resources::collision::CollisionStats;
+use crate::resources::terrain::TerrainHeightmap;
+use crate::resources::settings::{BoidSettings, CollisionSettings, GroupsTargets, IntegrationSettings, OrientationSettings, SimulationBackend};
+use crate::steering::registry::SteeringRegistry;
+use crate::systems::flocking::orient_boid;
+use crate::systems::integration::substep_count;
+
+pub const ECS_STEP_TIME: DiagnosticPath = DiagnosticPath::const_new("boids/ecs_step_time");
+pub const PACKED_STEP_TIME: DiagnosticPath = DiagnosticPath::const_new("boids/packed_step_time");
+
+pub fn ecs_backend(boid_settings: Res<BoidSettings>) -> bool {
+    boid_settings.simulation_backend == SimulationBackend::Ecs
+}
+
+pub fn packed_backend(boid_settings: Res<BoidSettings>) -> bool {
+    boid_settings.simulation_backend == SimulationBackend::Packed
+}
+
+pub fn sync_flock_core(
+    mut flock_core: ResMut<FlockCore>,
+    mut boid_query: Query<(Entity, &Transform, &Velocity, &Boid, &mut FlockSlot)>,
+    changed_query: Query<(), Changed<Boid>>,
+    mut removed_boids: RemovedComponents<Boid>,
+) {
+    // Reconstruire les tableaux dès qu'un boid est apparu, a disparu ou changé de groupe,
+    // même si la population totale est restée la même
+    let removed = removed_boids.read().count() > 0;
+    if !removed && changed_query.is_empty() && flock_core.len() == boid_query.iter().len() {
+        return;
+    }
+
+    flock_core.clear();
+    for (entity, transform, velocity, boid, mut slot) in boid_query.iter_mut() {
+        slot.0 = flock_core.push(entity, transform.translation, velocity.velocity, boid.group);
+    }
+}
+
+pub fn release_flock_core(mut flock_core: ResMut<FlockCore>) {
+    if !flock_core.is_empty() {
+        flock_core.clear();
+    }
+}
+
+/// Réglages dont le noyau compact tire ses `FlockParams`.
+#[derive(SystemParam)]
+pub struct FlockCoreSettings<'w> {
+    boid_settings: Res<'w, BoidSettings>,
+    orientation_settings: Res<'w, OrientationSettings>,
+    groups_targets: Res<'w, GroupsTargets>,
+    steering_registry: Res<'w, SteeringRegistry>,
+    integration_settings: Res<'w, IntegrationSettings>,
+    collision_settings: Res<'w, CollisionSettings>,
+    heightmap: Res<'w, TerrainHeightmap>,
+}
+
+impl FlockCoreSettings<'_> {
+    fn params(&self) -> FlockParams {
+        FlockParams::new(
+            &self.boid_settings,
+            &self.orientation_settings,
+            &self.groups_targets,
+            &self.steering_registry,
+            self.integration_settings.integrator,
+            &self.collision_settings,
+            &self.heightmap,
+        )
+    }
+}
+
+pub fn step_flock_core(
+    mut flock_core: ResMut<FlockCore>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Boid>>,
+    settings: FlockCoreSettings,
+    mut collision_stats: ResMut<CollisionStats>,
+    time: Res<Time>,
+    mut diagnostics: Diagnostics,
+) {
+    let obstacles = obstacle_query
+        .iter()
+        .map(|(transform, obstacle)| (transform.translation.into(), obstacle.radius))
+        .collect();
+    let params = FlockParams { obstacles, ..settings.params() };
+
+    let started = Instant::now();
+    let substeps = substep_count(time.delta_secs(), &settings.integration_settings);
+    for _ in 0..substeps {
+        let collisions = flock_core.step(&params, time.delta_secs() / substeps as f32);
+        collision_stats.record(collisions);
+    }
+    diagnostics.add_measurement(&PACKED_STEP_TIME, || started.elapsed().as_secs_f64() * 1000.0);
+}
+
+pub fn mirror_flock_core(
+    flock_core: Res<FlockCore>,
+    mut boid_query: Query<(&FlockSlot, &mut Transform, &mut Velocity, &mut SteeringForces), With<Boid>>,
+    orientation_settings: Res<OrientationSettings>,
+    time: Res<Time>,
+) {
+    boid_query.par_iter_mut().for_each(|(slot, mut transform, mut velocity, mut steering)| {
+        let (Some(position), Some(new_velocity)) = (flock_core.positions.get(slot.0), flock_core.velocities.get(slot.0)) else {
+            return;
+        };
+
+        transform.translation = (*position).into();
+        velocity.velocity = (*new_velocity).into();
+        steering.forces.clear();
+        steering.forces.extend(flock_core.steering[slot.0].iter().map(|force| Vec3::from(*force)));
+        transform.rotation = orient_boid(
+            transform.rotation,
+            velocity.velocity,
+            flock_core.accelerations[slot.0].into(),
+            &orientation_settings,
+            time.delta_secs(),
+        );
+    });
+}
+
+pub fn begin_ecs_step(mut step_timer: ResMut<FlockStepTimer>) {
+    step_timer.started = Some(Instant::now());
+}
+
+pub fn end_ecs_step(mut step_timer: ResMut<FlockStepTimer>, mut diagnostics: Diagnostics) {
+    if let Some(started) = step_timer.started.take() {
+        diagnostics.add_measurement(&ECS_STEP_TIME, || started.elapsed().as_secs_f64() * 1000.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use bevy_spatial::{AutomaticUpdate, SpatialSet, SpatialStructure};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use crate::components::boid::{Acceleration, ExternalForce, FlightState, IntegratorState};
+    use crate::components::spatial::TrackedByKDTree3D;
+    use crate::globals::{DEPTH, HEIGHT, MIN_HEIGHT, WIDTH};
+    use crate::resources::settings::{EnergySettings, PerceptionSettings, StartleSettings};
+    use crate::systems::collision::{collisions_enabled, resolve_obstacle_collisions};
+    use crate::systems::flocking::{apply_forces, confine_boids, flocking_system};
+    use crate::systems::integration::{final_phase, integrate_boids, run_flock_substeps, FlockStep};
+
+    const FRAME: Duration = Duration::from_micros(16_667);
+    const FRAMES: u32 = 3;
+
+    // Même tirage pour les deux chemins : positions dans le volume, vitesses à `min_speed`
+    fn sample_boids(count: usize, min_speed: f32) -> Vec<(Vec3, Vec3, u8)> {
+        let mut rng = StdRng::seed_from_u64(7);
+        (0..count)
+            .map(|_| {
+                let position = Vec3::new(
+                    rng.random_range(-WIDTH * 0.45..WIDTH * 0.45),
+                    rng.random_range(MIN_HEIGHT..HEIGHT * 0.95),
+                    rng.random_range(-DEPTH * 0.45..DEPTH * 0.45),
+                );
+                let direction = Vec3::new(rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0));
+                (position, direction.normalize_or(Vec3::X) * min_speed, rng.random_range(0..2))
+            })
+            .collect()
+    }
+
+    // Chaîne `FlockStep` de `BoidsPlugin`, KD-Tree reconstruit à chaque frame
+    fn ecs_app(boids: &[(Vec3, Vec3, u8)]) -> App {
+        let mut app = App::new();
+        app.insert_resource(Time::<()>::default())
+            .insert_resource(BoidSettings::default())
+            .insert_resource(GroupsTargets::default())
+            .insert_resource(OrientationSettings::default())
+            .insert_resource(IntegrationSettings::default())
+            .insert_resource(CollisionSettings::default())
+            .insert_resource(PerceptionSettings::default())
+            .insert_resource(EnergySettings::default())
+            .insert_resource(StartleSettings::default())
+            .init_resource::<TerrainHeightmap>()
+            .init_resource::<CollisionStats>()
+            .init_resource::<SteeringRegistry>()
+            .add_plugins(
+                AutomaticUpdate::<TrackedByKDTree3D>::new()
+                    .with_spatial_ds(SpatialStructure::KDTree3)
+                    .with_frequency(Duration::from_nanos(1)),
+            )
+            .add_systems(FlockStep, (
+                flocking_system,
+                apply_forces,
+                integrate_boids,
+                resolve_obstacle_collisions.run_if(final_phase.and(collisions_enabled)),
+                confine_boids.run_if(final_phase),
+            ).chain())
+            .add_systems(Update, run_flock_substeps.after(SpatialSet));
+
+        for (position, velocity, group) in boids {
+            app.world_mut().spawn((
+                Boid { group: *group },
+                Velocity { velocity: *velocity },
+                Acceleration { acceleration: Vec3::ZERO },
+                ExternalForce::default(),
+                SteeringForces::default(),
+                IntegratorState::default(),
+                FlightState::Flying,
+                TrackedByKDTree3D,
+                Transform::from_translation(*position),
+            ));
+        }
+        app
+    }
+
+    fn packed_core(boids: &[(Vec3, Vec3, u8)]) -> FlockCore {
+        let mut flock_core = FlockCore::default();
+        for (index, (position, velocity, group)) in boids.iter().enumerate() {
+            flock_core.push(Entity::from_raw(index as u32), *position, *velocity, *group);
+        }
+        flock_core
+    }
+
+    /// Compare les deux backends sur quelques frames à population fixe.
+    /// `cargo test --release -- --ignored --nocapture compare_backends`
+    #[test]
+    #[ignore]
+    fn compare_backends() {
+        let boid_settings = BoidSettings::default();
+        let integration_settings = IntegrationSettings::default();
+        let params = FlockParams::new(
+            &boid_settings,
+            &OrientationSettings::default(),
+            &GroupsTargets::default(),
+            &SteeringRegistry::default(),
+            integration_settings.integrator,
+            &CollisionSettings::default(),
+            &TerrainHeightmap::default(),
+        );
+        let substeps = substep_count(FRAME.as_secs_f32(), &integration_settings);
+
+        // Le volume est fixe : la densité, donc le nombre de voisins, croît avec la population
+        for count in [1_000, 3_000, 10_000] {
+            let boids = sample_boids(count, boid_settings.min_speed);
+
+            let mut app = ecs_app(&boids);
+            let started = Instant::now();
+            for _ in 0..FRAMES {
+                app.world_mut().resource_mut::<Time>().advance_by(FRAME);
+                app.update();
+            }
+            let ecs = started.elapsed() / FRAMES;
+
+            let mut flock_core = packed_core(&boids);
+            let started = Instant::now();
+            for _ in 0..FRAMES {
+                for _ in 0..substeps {
+                    flock_core.step(&params, FRAME.as_secs_f32() / substeps as f32);
+                }
+            }
+            let packed = started.elapsed() / FRAMES;
+
+            println!("{count:>7} boids : ECS {ecs:>10.2?} / frame, noyau compact {packed:>10.2?} / frame");
+        }
+    }
+}
@@ -1,207 +1,426 @@
+use std::sync::Mutex;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy_spatial::SpatialAccess;
-use std::sync::Mutex;
-use crate::components::boid::{Boid, Velocity, Acceleration, Obstacle};
+use crate::components::boid::{Boid, BoidTraits, Energy, ExternalForce, FlightState, Velocity, Acceleration, Obstacle, PerceptionHistory, SteeringForces};
 use crate::components::spatial::NNTree3D;
-use crate::resources::settings::{BoidSettings, GroupsTargets};
+use crate::resources::inspection::SelectedBoid;
+use crate::resources::settings::{BoidSettings, GroupsTargets, OrientationSettings, PerceptionSettings};
+use crate::resources::terrain::TerrainHeightmap;
+use crate::steering::registry::SteeringRegistry;
+use crate::steering::{BoidState, Neighbours, SteeringContext};
 use crate::events::ApplyForceEvent;
 use crate::globals::{WIDTH, HEIGHT, DEPTH, MIN_HEIGHT};
-
-pub fn flocking_system(
-    boid_query: Query<(Entity, &Transform, &Velocity, &Boid), With<Boid>>,
-    event_writer: EventWriter<ApplyForceEvent>,
-    boid_settings: Res<BoidSettings>,
-    groups_targets: Res<GroupsTargets>,
-    kd_tree: Res<NNTree3D>,
-) {
-    let event_writer = Mutex::new(event_writer);
-
-    boid_query.par_iter().for_each(|(entity, transform, velocity, boid)| {
-        let position = transform.translation;
-        let mut cohesion_neighbors: Vec<Vec3> = Vec::new();
-        let mut repulsion_neighbors: Vec<(Vec3, f32)> = Vec::new();
-        let mut alignment_neighbors: Vec<Vec3> = Vec::new();
-
-        // Utiliser le KD-Tree pour trouver les voisins
-        for (_, neighbor_entity) in kd_tree.within_distance(position, boid_settings.cohesion_range) {
-            if let Some(neighbor_entity) = neighbor_entity {
-                if neighbor_entity == entity { continue; }
-
-                if let Ok((_, neighbor_transform, neighbor_velocity, _)) = boid_query.get(neighbor_entity) {
-                    let neighbor_pos = neighbor_transform.translation;
-
-                    if let Some(distance) = is_in_field_of_view(&position, &velocity.velocity, &neighbor_pos, &boid_settings.field_of_view) {
-                        if distance < boid_settings.separation_range {
-                            repulsion_neighbors.push((neighbor_pos, distance));
-                        } else if distance < boid_settings.alignment_range {
-                            alignment_neighbors.push(neighbor_velocity.velocity);
-                        } else if distance < boid_settings.cohesion_range {
-                            cohesion_neighbors.push(neighbor_pos);
-                        }
-                    }
-                }
-            }
+use crate::systems::boids::standard_normal;
+
+/// État d'un voisin tel que perçu : lu dans son historique avec retard, puis bruité.
+pub fn perceive(
+    position: Vec3,
+    velocity: Vec3,
+    history: Option<&PerceptionHistory>,
+    perception: &PerceptionSettings,
+    now: f32,
+) -> (Vec3, Vec3) {
+    let (mut position, mut velocity) = (position, velocity);
+    if perception.reaction_delay > 0.0 && let Some(history) = history {
+        // Dernier état connu avant l'instant perçu, ou le plus ancien disponible
+        let seen_at = now - perception.reaction_delay;
+        if let Some((_, past_position, past_velocity)) = history
+            .samples
+            .iter()
+            .rev()
+            .find(|(time, _, _)| *time <= seen_at)
+            .or(history.samples.front())
+        {
+            (position, velocity) = (*past_position, *past_velocity);
         }
+    }
 
-        // Calculer les forces
-        let cohesion_force = cohesion(&position, &cohesion_neighbors, &boid_settings.cohesion_coeff);
-        let separation_force = separation(&position, &repulsion_neighbors, &boid_settings.separation_coeff);
-        let alignment_force = alignment(&velocity.velocity, &alignment_neighbors, &boid_settings.alignment_coeff);
+    if perception.position_noise > 0.0 || perception.velocity_noise > 0.0 {
+        let mut rng = rand::rng();
+        let mut noise = |sigma: f32| Vec3::new(standard_normal(&mut rng), standard_normal(&mut rng), standard_normal(&mut rng)) * sigma;
+        position += noise(perception.position_noise);
+        velocity += noise(perception.velocity_noise);
+    }
 
-        // Attraction vers la cible du groupe
-        let target = groups_targets.targets[boid.group as usize];
-        let attraction_force = attraction_to_target(&position, &target, &boid_settings.attraction_coeff);
+    (position, velocity)
+}
 
-        let total_force = cohesion_force + separation_force + alignment_force + attraction_force;
+// Le segment [from, to] traverse-t-il la sphère ?
+fn segment_hits_sphere(from: Vec3, to: Vec3, center: Vec3, radius: f32) -> bool {
+    let segment = to - from;
+    let along = ((center - from).dot(segment) / segment.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+    (from + segment * along).distance_squared(center) < radius * radius
+}
 
-        let mut event_writer = event_writer.lock().unwrap();
-        event_writer.write(ApplyForceEvent {
-            entity,
-            force: total_force
+/// Écarte les voisins cachés par un obstacle ou par un voisin plus proche, puis ne garde que les plus proches.
+/// `candidates` doit être trié par distance croissante.
+fn limit_perception(
+    candidates: &mut Vec<(Entity, Vec3, Vec3, f32)>,
+    position: Vec3,
+    perception: &PerceptionSettings,
+    obstacles: &[(Vec3, f32)],
+) {
+    if perception.obstacle_occlusion {
+        candidates.retain(|(_, neighbor_pos, _, _)| {
+            !obstacles
+                .iter()
+                .any(|(center, radius)| segment_hits_sphere(position, *neighbor_pos, *center, *radius))
         });
-    });
-}
+    }
 
-fn cohesion(position: &Vec3, cohesion_neighbors: &Vec<Vec3>, cohesion_coeff: &f32) -> Vec3 {
-    if cohesion_neighbors.is_empty() {
-        return Vec3::ZERO;
+    if perception.neighbour_occlusion {
+        let mut visible: Vec<(Entity, Vec3, Vec3, f32)> = Vec::with_capacity(candidates.len());
+        for candidate in candidates.drain(..) {
+            let direction = (candidate.1 - position).normalize_or_zero();
+            let hidden = visible.iter().any(|(_, nearer_pos, _, nearer_distance)| {
+                // Demi-angle sous lequel le voisin plus proche est vu
+                let cos_half_angle = (1.0 - (perception.body_radius / nearer_distance).min(1.0).powi(2)).sqrt();
+                direction.dot((*nearer_pos - position).normalize_or_zero()) > cos_half_angle
+            });
+            if !hidden {
+                visible.push(candidate);
+            }
+        }
+        *candidates = visible;
     }
 
-    let center: Vec3 = cohesion_neighbors.iter().sum::<Vec3>() / cohesion_neighbors.len() as f32;
-    (center - *position) * *cohesion_coeff
+    if perception.max_neighbours > 0 {
+        candidates.truncate(perception.max_neighbours);
+    }
 }
 
-fn separation(position: &Vec3, repulsion_neighbors: &Vec<(Vec3, f32)>, separation_coeff: &f32) -> Vec3 {
-    let mut separation_force = Vec3::ZERO;
+/// Monde et réglages partagés par toutes les recherches de voisins d'une étape.
+pub struct NeighbourSearch<'a> {
+    pub kd_tree: &'a NNTree3D,
+    pub boid_settings: &'a BoidSettings,
+    pub perception: &'a PerceptionSettings,
+    /// Centre et rayon de chaque obstacle
+    pub obstacles: &'a [(Vec3, f32)],
+}
 
-    for (other_pos, distance) in repulsion_neighbors {
-        if *distance > 0.0 {
-            separation_force += (*position - *other_pos) / *distance;
+/// Cherche les voisins dans le KD-Tree et les classe par distance.
+/// `lookup` renvoie la position et la vélocité perçues d'un voisin ; les traits du boid modulent portées et champ de vision.
+pub fn find_neighbours(
+    boid: &BoidState,
+    search: &NeighbourSearch,
+    lookup: impl Fn(Entity) -> Option<(Vec3, Vec3)>,
+) -> Neighbours {
+    let NeighbourSearch { kd_tree, boid_settings, perception, obstacles } = *search;
+    let (entity, position, velocity, traits) = (boid.entity, boid.position, boid.velocity, &boid.traits);
+    let mut neighbours = Neighbours::default();
+    // La bande de séparation couvre au moins la distance minimale entre boids
+    let separation_range = boid_settings.separation_range.max(boid_settings.min_distance_between_boids) * traits.range;
+    let alignment_range = boid_settings.alignment_range * traits.range;
+    let cohesion_range = boid_settings.cohesion_range * traits.range;
+    let field_of_view = FieldOfView::new(boid_settings, perception, traits);
+    let forward = velocity.try_normalize().unwrap_or(boid.heading);
+
+    let mut candidates = Vec::new();
+    for (_, neighbor_entity) in kd_tree.within_distance(position, cohesion_range) {
+        let Some(neighbor_entity) = neighbor_entity else { continue; };
+        if neighbor_entity == entity { continue; }
+
+        let Some((neighbor_pos, neighbor_velocity)) = lookup(neighbor_entity) else { continue; };
+
+        if let Some(distance) = is_in_field_of_view(&position, &forward, &neighbor_pos, &field_of_view) {
+            candidates.push((neighbor_entity, neighbor_pos, neighbor_velocity, distance));
+        }
+    }
+    candidates.sort_by(|a, b| a.3.total_cmp(&b.3));
+    limit_perception(&mut candidates, position, perception, obstacles);
+
+    // Un voisin proche d'une frontière contribue aux deux bandes qu'elle sépare
+    let ranges = [separation_range, alignment_range, cohesion_range];
+    for &(neighbor_entity, neighbor_pos, neighbor_velocity, distance) in &candidates {
+        let [separation, alignment, cohesion] = band_weights(distance, ranges, perception.band_softness);
+        if separation > 0.0 {
+            neighbours.separation.push((neighbor_entity, neighbor_pos, distance, separation));
+        }
+        if alignment > 0.0 {
+            neighbours.alignment.push((neighbor_entity, neighbor_pos, neighbor_velocity, alignment));
+        }
+        if cohesion > 0.0 {
+            neighbours.cohesion.push((neighbor_entity, neighbor_pos, cohesion));
         }
     }
+    neighbours.perceived = candidates;
 
-    separation_force * *separation_coeff
+    neighbours
 }
 
-fn alignment(velocity: &Vec3, alignment_neighbors: &Vec<Vec3>, alignment_coeff: &f32) -> Vec3 {
-    if alignment_neighbors.is_empty() {
-        return Vec3::ZERO;
+/// Mémorise l'état courant de chaque boid pour le retard de perception.
+pub fn record_perception_history(
+    mut query: Query<(&Transform, &Velocity, &mut PerceptionHistory), With<Boid>>,
+    perception: Res<PerceptionSettings>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    let oldest = now - perception.reaction_delay;
+
+    for (transform, velocity, mut history) in query.iter_mut() {
+        // Un historique interrompu (retard désactivé entre-temps) est périmé
+        if history.samples.back().is_some_and(|(time, _, _)| *time < oldest) {
+            history.samples.clear();
+        }
+        history.samples.push_back((now, transform.translation, velocity.velocity));
+        // Garder un état antérieur à la fenêtre pour couvrir tout le retard
+        while history.samples.get(1).is_some_and(|(time, _, _)| *time <= oldest) {
+            history.samples.pop_front();
+        }
     }
+}
 
-    let avg_velocity: Vec3 = alignment_neighbors.iter().sum::<Vec3>() / alignment_neighbors.len() as f32;
-    (avg_velocity - *velocity) * *alignment_coeff
+pub fn reaction_delayed(perception: Res<PerceptionSettings>) -> bool {
+    perception.reaction_delay > 0.0
 }
 
-fn attraction_to_target(position: &Vec3, target: &Vec3, attraction_coeff: &f32) -> Vec3 {
-    (*target - *position) * *attraction_coeff
+type PerceivedBoids<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static Velocity, &'static Boid, &'static FlightState, Option<&'static Energy>, Option<&'static BoidTraits>, Option<&'static PerceptionHistory>),
+>;
+
+/// Réglages lus par `flocking_system` pour évaluer les règles.
+#[derive(SystemParam)]
+pub struct FlockingSettings<'w> {
+    registry: Res<'w, SteeringRegistry>,
+    boid_settings: Res<'w, BoidSettings>,
+    perception: Res<'w, PerceptionSettings>,
+    groups_targets: Res<'w, GroupsTargets>,
 }
 
-fn is_in_field_of_view(position: &Vec3, velocity: &Vec3, other_pos: &Vec3, fov_degrees: &f32) -> Option<f32> {
-    let to_other = *other_pos - *position;
-    let distance = to_other.length();
+pub fn flocking_system(
+    boid_query: PerceivedBoids,
+    mut steering_query: Query<(Entity, &mut SteeringForces), With<Boid>>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Boid>>,
+    settings: FlockingSettings,
+    kd_tree: Res<NNTree3D>,
+    time: Res<Time>,
+    selected: Option<ResMut<SelectedBoid>>,
+) {
+    let FlockingSettings { registry, boid_settings, perception, groups_targets } = settings;
+    let now = time.elapsed_secs();
+    // Le bruit de perception est tiré ici : l'inspecteur relit ce tirage plutôt que d'en refaire un
+    let watched = selected.as_ref().and_then(|selected| selected.entity);
+    let perceived = Mutex::new(None);
+    let obstacles: Vec<(Vec3, f32)> = obstacle_query
+        .iter()
+        .map(|(obstacle_transform, obstacle)| (obstacle_transform.translation, obstacle.radius))
+        .collect();
+    let context = SteeringContext {
+        settings: &boid_settings,
+        targets: &groups_targets.targets,
+        obstacles: &obstacles,
+        elapsed_secs: now,
+    };
+    let search = NeighbourSearch {
+        kd_tree: &kd_tree,
+        boid_settings: &boid_settings,
+        perception: &perception,
+        obstacles: &obstacles,
+    };
+
+    steering_query.par_iter_mut().for_each(|(entity, mut steering)| {
+        let Ok((transform, velocity, boid, flight_state, energy, traits, _)) = boid_query.get(entity) else { return; };
+        let traits = traits.copied().unwrap_or_default();
+        let position = transform.translation;
 
-    if distance <= 0.0 || velocity.length_squared() == 0.0 {
-        return Some(distance);
-    }
+        // Les boids posés ou en approche ne suivent pas le groupe
+        if !flight_state.is_airborne() {
+            steering.forces.clear();
+            return;
+        }
 
-    let cos_fov = (fov_degrees / 2.0).to_radians().cos();
-    let dot = velocity.normalize().dot(to_other.normalize());
+        let state = BoidState {
+            entity,
+            position,
+            velocity: velocity.velocity,
+            heading: *transform.forward(),
+            group: boid.group,
+            energy: energy.map_or(1.0, |energy| energy.value),
+            traits,
+        };
 
-    if dot >= cos_fov {
-        Some(distance)
-    } else {
-        None
+        // Utiliser le KD-Tree pour trouver les voisins
+        let neighbours = find_neighbours(&state, &search, |other| {
+            boid_query
+                .get(other)
+                .ok()
+                .filter(|(_, _, _, other_state, _, _, _)| other_state.is_airborne())
+                .map(|(other_transform, other_velocity, _, _, _, _, history)| {
+                    perceive(other_transform.translation, other_velocity.velocity, history, &perception, now)
+                })
+        });
+
+        // Chaque règle enregistrée ajoute sa force pondérée
+        registry.evaluate(&state, &neighbours, &context, &mut steering);
+
+        if watched == Some(entity) && let Ok(mut perceived) = perceived.lock() {
+            *perceived = Some((entity, neighbours));
+        }
+    });
+
+    if let Some(mut selected) = selected && watched.is_some() {
+        selected.perceived = perceived.into_inner().ok().flatten();
     }
 }
 
-pub fn avoid_obstacles(
-    boid_query: Query<(Entity, &Transform), With<Boid>>,
-    obstacle_query: Query<(&Transform, &Obstacle), Without<Boid>>,
-    mut event_writer: EventWriter<ApplyForceEvent>,
-    boid_settings: Res<BoidSettings>,
-) {
-    for (entity, transform) in boid_query.iter() {
-        let position = transform.translation;
-        let mut avoidance_force = Vec3::ZERO;
-
-        for (obstacle_transform, obstacle) in obstacle_query.iter() {
-            let obstacle_pos = obstacle_transform.translation;
-            let to_obstacle = obstacle_pos - position;
-            let distance = to_obstacle.length();
+/// Forme du champ de vision d'un boid, angles en degrés.
+pub struct FieldOfView {
+    pub horizontal: f32,
+    // Sans champ vertical, la vision est un cône de révolution d'angle `horizontal`
+    pub vertical: Option<f32>,
+    pub blind_spot: f32,
+}
 
-            // Distance depuis la surface de l'obstacle
-            let surface_distance = distance - obstacle.radius;
+impl FieldOfView {
+    pub fn new(boid_settings: &BoidSettings, perception: &PerceptionSettings, traits: &BoidTraits) -> Self {
+        FieldOfView {
+            horizontal: (boid_settings.field_of_view * traits.field_of_view).min(360.0),
+            vertical: perception
+                .separate_vertical_fov
+                .then(|| (perception.vertical_field_of_view * traits.field_of_view).min(180.0)),
+            blind_spot: perception.blind_spot,
+        }
+    }
 
-            if surface_distance < boid_settings.separation_range * 2.0 && surface_distance > 0.0 {
-                // Force de répulsion inversement proportionnelle à la distance
-                let repulsion_dir = (position - obstacle_pos).normalize_or_zero();
-                let strength = 1.0 - (surface_distance / (boid_settings.separation_range * 2.0));
-                avoidance_force += repulsion_dir * strength * boid_settings.collision_coeff;
-            }
+    /// La direction unitaire `direction` est-elle visible pour un boid orienté selon `forward` ?
+    pub fn contains(&self, forward: Vec3, direction: Vec3) -> bool {
+        if self.blind_spot > 0.0 && direction.dot(-forward) > (self.blind_spot / 2.0).to_radians().cos() {
+            return false;
         }
 
-        event_writer.write(ApplyForceEvent {
-            entity,
-            force: avoidance_force,
-        });
+        let Some(vertical) = self.vertical else {
+            return direction.dot(forward) >= (self.horizontal / 2.0).to_radians().cos();
+        };
+
+        // Azimut dans le plan horizontal du boid, élévation au-dessus de ce plan
+        let (right, up) = view_axes(forward);
+        let azimuth = direction.dot(right).atan2(direction.dot(forward));
+        let elevation = direction.dot(up).clamp(-1.0, 1.0).asin();
+        azimuth.abs() <= (self.horizontal / 2.0).to_radians() && elevation.abs() <= (vertical / 2.0).to_radians()
     }
 }
 
-pub fn apply_forces(
+/// Axes droite et haut du repère de vision, le plan horizontal restant celui du monde.
+pub fn view_axes(forward: Vec3) -> (Vec3, Vec3) {
+    let right = forward.cross(Vec3::Y).try_normalize().unwrap_or(Vec3::X);
+    (right, right.cross(forward))
+}
+
+// Passage progressif de 0 à 1 autour de `edge`, net si `softness` est nulle
+fn band_edge(distance: f32, edge: f32, softness: f32) -> f32 {
+    if softness <= 0.0 {
+        return if distance < edge { 0.0 } else { 1.0 };
+    }
+    let t = ((distance - edge) / softness + 0.5).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Poids de séparation, d'alignement et de cohésion d'un voisin selon sa distance.
+/// Sans transition, chaque voisin appartient à une seule bande, comme avec des seuils nets.
+fn band_weights(distance: f32, [separation_range, alignment_range, cohesion_range]: [f32; 3], softness: f32) -> [f32; 3] {
+    let beyond_separation = band_edge(distance, separation_range, softness);
+    let beyond_alignment = band_edge(distance, alignment_range, softness);
+    // La cohésion s'éteint avant la portée, là où le KD-Tree ne cherche plus
+    let within_cohesion = 1.0 - band_edge(distance, cohesion_range - softness * 0.5, softness);
+
+    [
+        1.0 - beyond_separation,
+        beyond_separation * (1.0 - beyond_alignment),
+        beyond_alignment * within_cohesion,
+    ]
+}
+
+// Distance au voisin s'il est visible. Un voisin confondu avec le boid n'a pas de direction et n'est pas vu.
+fn is_in_field_of_view(position: &Vec3, forward: &Vec3, other_pos: &Vec3, field_of_view: &FieldOfView) -> Option<f32> {
+    let to_other = *other_pos - *position;
+    let direction = to_other.try_normalize()?;
+
+    field_of_view.contains(*forward, direction).then_some(to_other.length())
+}
+
+/// Lit une fois par frame les forces ponctuelles, pour que chaque sous-étape et
+/// chaque phase RK2 les voient toutes, au lieu de la seule première.
+pub fn collect_external_forces(
     mut events: EventReader<ApplyForceEvent>,
-    mut query: Query<&mut Acceleration, With<Boid>>,
+    mut query: Query<&mut ExternalForce, With<Boid>>,
 ) {
+    for mut external in query.iter_mut() {
+        external.force = Vec3::ZERO;
+    }
+
     for event in events.read() {
-        if let Ok(mut acceleration) = query.get_mut(event.entity) {
-            acceleration.acceleration += event.force;
+        if let Ok(mut external) = query.get_mut(event.entity) {
+            external.force += event.force;
         }
     }
 }
 
-pub fn update_boids(
-    mut query: Query<(&mut Transform, &mut Velocity, &mut Acceleration), With<Boid>>,
+// L'intégrateur reçoit les règles combinées, plus les forces ponctuelles de la frame
+pub fn apply_forces(
+    mut query: Query<(&SteeringForces, &ExternalForce, &mut Acceleration), With<Boid>>,
+    registry: Res<SteeringRegistry>,
     boid_settings: Res<BoidSettings>,
-    time: Res<Time>,
 ) {
-    for (mut transform, mut velocity, mut acceleration) in query.iter_mut() {
-        // Appliquer l'accélération à la vélocité
-        velocity.velocity += acceleration.acceleration * time.delta_secs();
-
-        // Limiter la vitesse
-        let speed = velocity.velocity.length();
-        if speed > 0.0 {
-            if speed < boid_settings.min_speed {
-                velocity.velocity = velocity.velocity.normalize() * boid_settings.min_speed;
-            } else if speed > boid_settings.max_speed {
-                velocity.velocity = velocity.velocity.normalize() * boid_settings.max_speed;
-            }
-        }
-
-        // Mettre à jour la position
-        transform.translation += velocity.velocity * time.delta_secs();
+    for (steering, external, mut acceleration) in query.iter_mut() {
+        acceleration.acceleration += registry.combine(steering, &boid_settings) + external.force;
+    }
+}
 
-        // Orienter le boid dans la direction du mouvement
-        if velocity.velocity.length_squared() > 0.0 {
-            let forward = -velocity.velocity.normalize();
-            transform.rotation = Quat::from_rotation_arc(Vec3::Z, forward);
+pub fn orient_boid(
+    current: Quat,
+    velocity: Vec3,
+    acceleration: Vec3,
+    orientation_settings: &OrientationSettings,
+    delta_secs: f32,
+) -> Quat {
+    let Some(forward) = velocity.try_normalize() else {
+        return current;
+    };
+
+    // Inclinaison vers l'intérieur du virage, d'après l'accélération latérale
+    let right = forward.cross(Vec3::Y).normalize_or_zero();
+    let up = if right == Vec3::ZERO {
+        current * Vec3::Y
+    } else {
+        let max_bank = orientation_settings.max_bank_angle.to_radians();
+        let lateral = acceleration.dot(right);
+        let bank = (lateral * orientation_settings.banking_coeff).atan().clamp(-max_bank, max_bank);
+        Quat::from_axis_angle(forward, bank) * Vec3::Y
+    };
+    let target = Transform::IDENTITY.looking_to(forward, up).rotation;
+
+    let mut next = if orientation_settings.smoothing > 0.0 {
+        current.slerp(target, 1.0 - (-orientation_settings.smoothing * delta_secs).exp())
+    } else {
+        target
+    };
+
+    if orientation_settings.max_turn_rate > 0.0 {
+        let angle = current.angle_between(next);
+        let max_angle = orientation_settings.max_turn_rate.to_radians() * delta_secs;
+        if angle > max_angle {
+            next = current.slerp(next, max_angle / angle);
         }
-
-        // Réinitialiser l'accélération
-        acceleration.acceleration = Vec3::ZERO;
     }
+
+    next
 }
 
 pub fn confine_boids(
-    mut query: Query<(&mut Transform, &mut Velocity), With<Boid>>,
+    mut query: Query<(&mut Transform, &mut Velocity, &FlightState), With<Boid>>,
     boid_settings: Res<BoidSettings>,
+    heightmap: Res<TerrainHeightmap>,
 ) {
     let margin = 10.0;
     let turn_factor = 10.0;
 
-    for (transform, mut velocity) in query.iter_mut() {
+    for (transform, mut velocity, flight_state) in query.iter_mut() {
         let pos = transform.translation;
 
-        if boid_settings.bounce_against_walls {
+        if boid_settings.bounce_against_walls && *flight_state == FlightState::Flying {
             // Confinement horizontal
             if pos.x < -WIDTH / 2.0 + margin {
                 velocity.velocity.x += turn_factor;
@@ -209,8 +428,9 @@ pub fn confine_boids(
                 velocity.velocity.x -= turn_factor;
             }
 
-            // Confinement vertical
-            if pos.y < MIN_HEIGHT + margin {
+            // Confinement vertical, le plancher suit le relief
+            let floor = heightmap.height_at(pos.x, pos.z) + MIN_HEIGHT;
+            if pos.y < floor + margin {
                 velocity.velocity.y += turn_factor;
             } else if pos.y > HEIGHT - margin {
                 velocity.velocity.y -= turn_factor;
@@ -224,4 +444,88 @@ pub fn confine_boids(
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> PerceptionHistory {
+        PerceptionHistory {
+            samples: [(0.0, Vec3::ZERO, Vec3::X), (0.5, Vec3::Y, Vec3::Y), (1.0, Vec3::Z, Vec3::Z)].into(),
+        }
+    }
+
+    #[test]
+    fn perceive_without_delay_or_noise_is_exact() {
+        let perception = PerceptionSettings::default();
+        let seen = perceive(Vec3::ONE, Vec3::X, Some(&history()), &perception, 1.0);
+        assert_eq!(seen, (Vec3::ONE, Vec3::X));
+    }
+
+    #[test]
+    fn perceive_reads_the_last_sample_before_the_delay() {
+        let perception = PerceptionSettings { reaction_delay: 0.4, ..default() };
+        let seen = perceive(Vec3::ONE, Vec3::X, Some(&history()), &perception, 1.0);
+        assert_eq!(seen, (Vec3::Y, Vec3::Y));
+    }
+
+    #[test]
+    fn perceive_falls_back_to_the_oldest_sample() {
+        let perception = PerceptionSettings { reaction_delay: 5.0, ..default() };
+        let seen = perceive(Vec3::ONE, Vec3::X, Some(&history()), &perception, 1.0);
+        assert_eq!(seen, (Vec3::ZERO, Vec3::X));
+
+        // Sans historique, l'état courant est perçu
+        assert_eq!(perceive(Vec3::ONE, Vec3::X, None, &perception, 1.0), (Vec3::ONE, Vec3::X));
+    }
+
+    #[test]
+    fn segment_hits_sphere_between_its_ends() {
+        let from = Vec3::new(-5.0, 0.0, 0.0);
+        let to = Vec3::new(5.0, 0.0, 0.0);
+        assert!(segment_hits_sphere(from, to, Vec3::new(0.0, 0.5, 0.0), 1.0));
+        assert!(!segment_hits_sphere(from, to, Vec3::new(0.0, 2.0, 0.0), 1.0));
+    }
+
+    #[test]
+    fn segment_hits_sphere_ignores_spheres_beyond_its_ends() {
+        let from = Vec3::ZERO;
+        let to = Vec3::new(5.0, 0.0, 0.0);
+        // Sur la droite mais derrière le point de départ ou au-delà de l'arrivée
+        assert!(!segment_hits_sphere(from, to, Vec3::new(-3.0, 0.0, 0.0), 1.0));
+        assert!(!segment_hits_sphere(from, to, Vec3::new(8.0, 0.0, 0.0), 1.0));
+        // Segment dégénéré : seul le point compte
+        assert!(segment_hits_sphere(from, from, Vec3::new(0.5, 0.0, 0.0), 1.0));
+    }
+
+    #[test]
+    fn band_weights_are_exclusive_without_softness() {
+        let ranges = [10.0, 20.0, 30.0];
+        assert_eq!(band_weights(5.0, ranges, 0.0), [1.0, 0.0, 0.0]);
+        assert_eq!(band_weights(15.0, ranges, 0.0), [0.0, 1.0, 0.0]);
+        assert_eq!(band_weights(25.0, ranges, 0.0), [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn band_weights_blend_across_soft_edges() {
+        let ranges = [10.0, 20.0, 30.0];
+        let [separation, alignment, cohesion] = band_weights(10.0, ranges, 4.0);
+        assert!((separation - 0.5).abs() < 1e-5 && (alignment - 0.5).abs() < 1e-5);
+        assert_eq!(cohesion, 0.0);
+
+        // La cohésion est éteinte à la portée de recherche
+        assert_eq!(band_weights(30.0, ranges, 4.0)[2], 0.0);
+    }
+
+    #[test]
+    fn field_of_view_uses_heading_and_rejects_coincident_neighbours() {
+        let boid_settings = BoidSettings { field_of_view: 180.0, ..default() };
+        let field_of_view = FieldOfView::new(&boid_settings, &PerceptionSettings::default(), &BoidTraits::default());
+        let forward = Vec3::Z;
+
+        assert_eq!(is_in_field_of_view(&Vec3::ZERO, &forward, &Vec3::new(0.0, 0.0, 3.0), &field_of_view), Some(3.0));
+        assert_eq!(is_in_field_of_view(&Vec3::ZERO, &forward, &Vec3::new(0.0, 0.0, -3.0), &field_of_view), None);
+        assert_eq!(is_in_field_of_view(&Vec3::ZERO, &forward, &Vec3::ZERO, &field_of_view), None);
+    }
+}
@@ -0,0 +1,209 @@
+use std::f32::consts::PI;
+use bevy::prelude::*;
+use crate::components::boid::{Boid, FlightState, Velocity};
+use crate::resources::formation::{FormationSlot, FormationState};
+use crate::resources::leaders::GroupLeaders;
+use crate::resources::settings::{FormationSettings, FormationShape, GroupsTargets, LeaderSettings};
+
+const SLOT_COLOR: Color = Color::srgb(0.4, 0.5, 1.0);
+const SLOT_MARKER_SIZE: f32 = 1.0;
+
+/// Décalage de la place `index` dans le repère du groupe (droite, haut, avant).
+/// La place 0 est la tête de la formation, occupée par le meneur s'il y en a un.
+fn slot_offset(shape: FormationShape, index: usize, count: usize, spacing: f32) -> Vec3 {
+    let side = if index % 2 == 1 { 1.0 } else { -1.0 };
+    let rank = index.div_ceil(2) as f32;
+
+    match shape {
+        FormationShape::V => Vec3::new(side * rank, 0.0, -rank) * spacing,
+        FormationShape::Echelon => Vec3::new(index as f32, 0.0, -(index as f32)) * spacing,
+        FormationShape::LineAbreast => Vec3::new(side * rank, 0.0, 0.0) * spacing,
+        FormationShape::Grid => {
+            let columns = (count as f32).sqrt().ceil().max(1.0) as usize;
+            let column = (index % columns) as f32 - (columns - 1) as f32 * 0.5;
+            let row = (index / columns) as f32;
+            Vec3::new(column, 0.0, -row) * spacing
+        }
+        FormationShape::SphereShell => {
+            // Sphère de Fibonacci dont la surface laisse environ `spacing²` par place
+            let radius = spacing * (count as f32 / (4.0 * PI)).sqrt().max(1.0);
+            let golden_angle = PI * (3.0 - 5.0_f32.sqrt());
+            let y = 1.0 - 2.0 * (index as f32 + 0.5) / count.max(1) as f32;
+            let ring = (1.0 - y * y).sqrt();
+            let theta = golden_angle * index as f32;
+            Vec3::new(ring * theta.cos(), y, ring * theta.sin()) * radius
+        }
+    }
+}
+
+/// Attribue une place à chaque membre : un boid garde sa place précédente tant qu'elle existe
+/// et reste libre, les autres prennent la place libre la plus proche.
+/// Renvoie pour chaque membre un indice dans `slots`.
+fn assign_slots(positions: &[Vec3], previous: &[Option<usize>], slots: &[Vec3]) -> Vec<usize> {
+    let mut taken = vec![false; slots.len()];
+    let mut assigned: Vec<Option<usize>> = previous
+        .iter()
+        .map(|slot| {
+            let slot = (*slot).filter(|slot| *slot < slots.len() && !taken[*slot])?;
+            taken[slot] = true;
+            Some(slot)
+        })
+        .collect();
+
+    for (member, slot) in assigned.iter_mut().enumerate() {
+        if slot.is_some() {
+            continue;
+        }
+        *slot = (0..slots.len())
+            .filter(|candidate| !taken[*candidate])
+            .min_by(|a, b| {
+                slots[*a].distance_squared(positions[member]).total_cmp(&slots[*b].distance_squared(positions[member]))
+            });
+        if let Some(slot) = *slot {
+            taken[slot] = true;
+        }
+    }
+
+    assigned.into_iter().map(|slot| slot.unwrap_or_default()).collect()
+}
+
+/// Avance le fondu et recalcule les places de chaque groupe.
+pub fn assign_formation_slots(
+    boid_query: Query<(Entity, &Transform, &Velocity, &Boid, &FlightState)>,
+    formation_settings: Res<FormationSettings>,
+    groups_targets: Res<GroupsTargets>,
+    leader_settings: Res<LeaderSettings>,
+    group_leaders: Res<GroupLeaders>,
+    mut formation_state: ResMut<FormationState>,
+    time: Res<Time>,
+) {
+    let goal = if formation_settings.enabled { 1.0 } else { 0.0 };
+    let step = time.delta_secs() / formation_settings.blend_time.max(f32::EPSILON);
+    formation_state.blend = formation_state.blend + (goal - formation_state.blend).clamp(-step, step);
+
+    formation_state.slots.clear();
+    if formation_state.blend <= 0.0 {
+        formation_state.assignments.clear();
+        return;
+    }
+    let previous_assignments = std::mem::take(&mut formation_state.assignments);
+
+    for (group, target) in groups_targets.targets.iter().enumerate() {
+        let mut members: Vec<(Entity, Vec3, Vec3)> = boid_query
+            .iter()
+            .filter(|(_, _, _, boid, flight_state)| boid.group as usize == group && flight_state.is_airborne())
+            .map(|(entity, transform, velocity, _, _)| (entity, transform.translation, velocity.velocity))
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        members.sort_by_key(|(entity, _, _)| *entity);
+
+        // Repère du groupe : le premier meneur s'il y en a un, sinon le centre du groupe
+        let leader = group_leaders
+            .groups
+            .get(group)
+            .filter(|_| leader_settings.enabled)
+            .and_then(|leaders| leaders.first())
+            .and_then(|leader| members.iter().position(|(entity, _, _)| entity == leader))
+            .map(|index| members.remove(index));
+        let (origin, velocity) = leader.map_or_else(
+            || {
+                let count = members.len() as f32;
+                (
+                    members.iter().map(|(_, position, _)| *position).sum::<Vec3>() / count,
+                    members.iter().map(|(_, _, velocity)| *velocity).sum::<Vec3>() / count,
+                )
+            },
+            |(_, position, velocity)| (position, velocity),
+        );
+
+        let forward = velocity
+            .try_normalize()
+            .or_else(|| (*target - origin).try_normalize())
+            .unwrap_or(Vec3::X);
+        let right = forward.cross(Vec3::Y).try_normalize().unwrap_or(Vec3::Z);
+        let up = right.cross(forward);
+
+        let first_slot = usize::from(leader.is_some());
+        let count = members.len() + first_slot;
+        let shape = formation_settings.shape;
+        let spacing = formation_settings.spacing;
+        // Le meneur occupe la place 0 : le repère est décalé pour qu'elle tombe sur lui,
+        // ce qui le met sur la coque plutôt qu'au centre de la sphère
+        let anchor = if leader.is_some() { slot_offset(shape, 0, count, spacing) } else { Vec3::ZERO };
+        let slots: Vec<Vec3> = (first_slot..count)
+            .map(|index| {
+                let local = slot_offset(shape, index, count, spacing) - anchor;
+                origin + right * local.x + up * local.y + forward * local.z
+            })
+            .collect();
+
+        let positions: Vec<Vec3> = members.iter().map(|(_, position, _)| *position).collect();
+        let previous: Vec<Option<usize>> = members
+            .iter()
+            .map(|(entity, _, _)| previous_assignments.get(entity).and_then(|index| index.checked_sub(first_slot)))
+            .collect();
+        for ((entity, _, _), slot) in members.iter().zip(assign_slots(&positions, &previous, &slots)) {
+            formation_state.assignments.insert(*entity, first_slot + slot);
+            formation_state.slots.insert(*entity, FormationSlot {
+                position: slots[slot],
+                velocity,
+            });
+        }
+    }
+}
+
+pub fn draw_formation_slots(
+    mut gizmos: Gizmos,
+    formation_settings: Res<FormationSettings>,
+    formation_state: Res<FormationState>,
+) {
+    if !formation_settings.show_slots {
+        return;
+    }
+
+    for slot in formation_state.slots.values() {
+        gizmos.cross(slot.position, SLOT_MARKER_SIZE, SLOT_COLOR.with_alpha(formation_state.blend));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sphere_shell_slots_share_the_same_radius() {
+        let count = 12;
+        let radius = slot_offset(FormationShape::SphereShell, 0, count, 2.0).length();
+        assert!(radius > 0.0);
+        for index in 1..count {
+            let offset = slot_offset(FormationShape::SphereShell, index, count, 2.0);
+            assert!((offset.length() - radius).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn v_wings_alternate_and_trail_the_head() {
+        assert_eq!(slot_offset(FormationShape::V, 0, 5, 2.0), Vec3::ZERO);
+        assert_eq!(slot_offset(FormationShape::V, 1, 5, 2.0), Vec3::new(2.0, 0.0, -2.0));
+        assert_eq!(slot_offset(FormationShape::V, 2, 5, 2.0), Vec3::new(-2.0, 0.0, -2.0));
+    }
+
+    #[test]
+    fn members_keep_their_previous_slot() {
+        let slots = [Vec3::ZERO, Vec3::X, Vec3::Y];
+        let positions = [Vec3::Y, Vec3::ZERO, Vec3::X];
+        let assigned = assign_slots(&positions, &[Some(0), Some(1), Some(2)], &slots);
+        assert_eq!(assigned, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn newcomers_take_the_nearest_free_slot() {
+        let slots = [Vec3::ZERO, Vec3::X, Vec3::Y];
+        // La place 1 est prise, la place 2 est hors de portée après un départ
+        let positions = [Vec3::X, Vec3::new(0.1, 0.0, 0.0), Vec3::Y];
+        let assigned = assign_slots(&positions, &[Some(1), None, Some(5)], &slots);
+        assert_eq!(assigned, vec![1, 0, 2]);
+    }
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::input::EguiWantsInput;
+use crate::components::boid::{Boid, BoidTraits, Energy, FlightState, SteeringForces, Velocity};
+use crate::resources::inspection::{BoidReport, SelectedBoid};
+use crate::systems::picking::{cursor_ray, pick_boid, PICK_RADIUS};
+use crate::steering::behaviours::{ALIGNMENT_COLOR, COHESION_COLOR, SEPARATION_COLOR};
+
+// Déplacement maximal du curseur (en pixels) pour qu'un appui compte comme un clic
+const CLICK_TOLERANCE: f32 = 4.0;
+
+const SELECTION_COLOR: Color = Color::WHITE;
+
+pub fn select_boid_on_click(
+    mut selected: ResMut<SelectedBoid>,
+    mut press_position: Local<Option<Vec2>>,
+    egui_wants_input: Res<EguiWantsInput>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera3d>>,
+    boid_query: Query<(Entity, &Transform), With<Boid>>,
+) {
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        *press_position = if egui_wants_input.wants_any_pointer_input() {
+            None
+        } else {
+            window.cursor_position()
+        };
+    }
+
+    if !mouse_buttons.just_released(MouseButton::Left) {
+        return;
+    }
+
+    // Un glisser sert à tourner la caméra, pas à sélectionner
+    let Some(pressed_at) = press_position.take() else {
+        return;
+    };
+    if window.cursor_position().is_none_or(|cursor| cursor.distance(pressed_at) > CLICK_TOLERANCE) {
+        return;
+    }
+
+    let (camera, camera_transform) = *camera;
+    let Some(ray) = cursor_ray(&window, camera, camera_transform) else {
+        return;
+    };
+
+    let boids = boid_query.iter().map(|(entity, transform)| (entity, transform.translation));
+    selected.entity = pick_boid(ray, boids, PICK_RADIUS).map(|(entity, _)| entity);
+}
+
+type InspectedBoids<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static Velocity, &'static Boid, &'static SteeringForces, &'static FlightState, Option<&'static Energy>, Option<&'static BoidTraits>),
+>;
+
+pub fn inspect_selected_boid(mut selected: ResMut<SelectedBoid>, boid_query: InspectedBoids) {
+    let Some(entity) = selected.entity else {
+        selected.report = None;
+        return;
+    };
+
+    // Le boid a pu être supprimé en réduisant le nombre de boids
+    let Ok((transform, velocity, boid, steering, flight_state, energy, traits)) = boid_query.get(entity) else {
+        selected.entity = None;
+        selected.report = None;
+        return;
+    };
+
+    // Les voisins sont ceux réellement perçus par `flocking_system`, bruit compris
+    let neighbours = selected
+        .perceived
+        .as_ref()
+        .filter(|(perceiver, _)| *perceiver == entity)
+        .map(|(_, neighbours)| neighbours.clone())
+        .unwrap_or_default();
+
+    selected.report = Some(BoidReport {
+        group: boid.group,
+        flight_state: *flight_state,
+        energy: energy.map(|energy| (energy.value, energy.drafting)),
+        traits: traits.copied(),
+        position: transform.translation,
+        velocity: velocity.velocity,
+        forces: steering.clone(),
+        neighbours,
+    });
+}
+
+pub fn highlight_selection(
+    mut gizmos: Gizmos,
+    selected: Res<SelectedBoid>,
+    boid_query: Query<&Transform, With<Boid>>,
+) {
+    let Some(report) = &selected.report else {
+        return;
+    };
+
+    gizmos.sphere(report.position, PICK_RADIUS, SELECTION_COLOR);
+
+    let neighbours = &report.neighbours;
+    let separation = neighbours.separation.iter().map(|(entity, _, _, _)| (*entity, SEPARATION_COLOR));
+    let alignment = neighbours.alignment.iter().map(|(entity, _, _, _)| (*entity, ALIGNMENT_COLOR));
+    let cohesion = neighbours.cohesion.iter().map(|(entity, _, _)| (*entity, COHESION_COLOR));
+
+    for (entity, color) in separation.chain(alignment).chain(cohesion) {
+        if let Ok(transform) = boid_query.get(entity) {
+            gizmos.line(report.position, transform.translation, color);
+            gizmos.sphere(transform.translation, PICK_RADIUS * 0.5, color);
+        }
+    }
+}
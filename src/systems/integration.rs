@@ -0,0 +1,337 @@
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+use crate::components::boid::{Acceleration, Boid, BoidTraits, Energy, FlightState, IntegratorState, Startle, Velocity};
+use crate::resources::settings::{BoidSettings, EnergySettings, IntegrationSettings, Integrator, OrientationSettings, StartleSettings};
+use crate::systems::flocking::orient_boid;
+
+/// Une sous-étape de simulation ECS : forces, intégration, confinement.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlockStep;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StepPhase {
+    // Étape complète en une seule évaluation des forces
+    Single,
+    // RK2 : déplacement vers l'état prédit
+    Predict,
+    // RK2 : retour à l'état de départ corrigé par les deux pentes
+    Correct,
+}
+
+/// Pas de temps et phase de la sous-étape en cours.
+#[derive(Resource)]
+pub struct SimulationStep {
+    pub delta_secs: f32,
+    pub phase: StepPhase,
+}
+
+impl SimulationStep {
+    pub fn is_final(&self) -> bool {
+        self.phase != StepPhase::Predict
+    }
+}
+
+pub fn substep_count(delta_secs: f32, integration_settings: &IntegrationSettings) -> u32 {
+    if integration_settings.max_substep <= 0.0 || delta_secs <= integration_settings.max_substep {
+        return 1;
+    }
+
+    ((delta_secs / integration_settings.max_substep).ceil() as u32).clamp(1, integration_settings.max_substeps.max(1))
+}
+
+pub fn clamp_speed(velocity: Vec3, min_speed: f32, max_speed: f32) -> Vec3 {
+    let speed = velocity.length();
+    if speed > 0.0 {
+        if speed < min_speed {
+            return velocity * (min_speed / speed);
+        } else if speed > max_speed {
+            return velocity * (max_speed / speed);
+        }
+    }
+    velocity
+}
+
+/// Limite l'angle entre deux vitesses successives à `max_angle` (radians), en gardant la norme de `next`.
+pub fn limit_turn(previous: Vec3, next: Vec3, max_angle: f32) -> Vec3 {
+    let (Some(from), Some(to)) = (previous.try_normalize(), next.try_normalize()) else {
+        return next;
+    };
+    let angle = from.angle_between(to);
+    if angle <= max_angle {
+        return next;
+    }
+
+    // Demi-tour exact : n'importe quel axe perpendiculaire convient
+    let axis = from.cross(to).try_normalize().unwrap_or_else(|| from.any_orthonormal_vector());
+    Quat::from_axis_angle(axis, max_angle) * from * next.length()
+}
+
+/// Position, vitesse et accélération d'un boid à un instant donné.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Kinematics {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub acceleration: Vec3,
+}
+
+/// Bornes de vitesse propres à un boid pour le pas en cours.
+#[derive(Clone, Copy, Debug)]
+pub struct SpeedLimits {
+    pub min_speed: f32,
+    pub max_speed: f32,
+}
+
+impl SpeedLimits {
+    pub fn clamp(&self, velocity: Vec3) -> Vec3 {
+        clamp_speed(velocity, self.min_speed, self.max_speed)
+    }
+}
+
+/// Avance une position et une vitesse d'un pas avec un intégrateur à une évaluation.
+/// RK2 se rabat ici sur sa phase de prédiction (Euler explicite).
+pub fn integrate(
+    integrator: Integrator,
+    current: Kinematics,
+    previous_acceleration: Vec3,
+    limits: SpeedLimits,
+    delta_secs: f32,
+) -> (Vec3, Vec3) {
+    let Kinematics { position, velocity, acceleration } = current;
+    match integrator {
+        Integrator::ExplicitEuler | Integrator::Rk2 => {
+            let next_position = position + velocity * delta_secs;
+            (next_position, limits.clamp(velocity + acceleration * delta_secs))
+        }
+        Integrator::SemiImplicitEuler => {
+            let next_velocity = limits.clamp(velocity + acceleration * delta_secs);
+            (position + next_velocity * delta_secs, next_velocity)
+        }
+        Integrator::Verlet => {
+            // Fin du demi-pas de vitesse de l'étape précédente, puis position au second ordre
+            let next_velocity = limits.clamp(velocity + (previous_acceleration + acceleration) * 0.5 * delta_secs);
+            let next_position = position + next_velocity * delta_secs + acceleration * 0.5 * delta_secs * delta_secs;
+            (next_position, next_velocity)
+        }
+    }
+}
+
+/// Correction de Heun : moyenne des vitesses et des accélérations des deux phases.
+/// `predicted.position` n'intervient pas, le pas repart de `start`.
+pub fn rk2_correct(start: Kinematics, predicted: Kinematics, limits: SpeedLimits, delta_secs: f32) -> (Vec3, Vec3) {
+    let position = start.position + (start.velocity + predicted.velocity) * 0.5 * delta_secs;
+    let velocity = start.velocity + (start.acceleration + predicted.acceleration) * 0.5 * delta_secs;
+    (position, limits.clamp(velocity))
+}
+
+pub fn final_phase(step: Res<SimulationStep>) -> bool {
+    step.is_final()
+}
+
+/// Découpe la frame en sous-étapes et exécute `FlockStep` pour chacune.
+pub fn run_flock_substeps(world: &mut World) {
+    let delta_secs = world.resource::<Time>().delta_secs();
+    let integration_settings = *world.resource::<IntegrationSettings>();
+    let substeps = substep_count(delta_secs, &integration_settings);
+
+    let phases: &[StepPhase] = match integration_settings.integrator {
+        Integrator::Rk2 => &[StepPhase::Predict, StepPhase::Correct],
+        _ => &[StepPhase::Single],
+    };
+
+    for _ in 0..substeps {
+        for phase in phases {
+            world.insert_resource(SimulationStep {
+                delta_secs: delta_secs / substeps as f32,
+                phase: *phase,
+            });
+            world.run_schedule(FlockStep);
+        }
+    }
+}
+
+type IntegratedBoids<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        &'static mut Velocity,
+        &'static mut Acceleration,
+        &'static mut IntegratorState,
+        &'static FlightState,
+        Option<&'static Energy>,
+        Option<&'static Startle>,
+        Option<&'static BoidTraits>,
+    ),
+    With<Boid>,
+>;
+
+pub fn integrate_boids(
+    mut query: IntegratedBoids,
+    boid_settings: Res<BoidSettings>,
+    energy_settings: Res<EnergySettings>,
+    startle_settings: Res<StartleSettings>,
+    integration_settings: Res<IntegrationSettings>,
+    orientation_settings: Res<OrientationSettings>,
+    step: Res<SimulationStep>,
+) {
+    let delta_secs = step.delta_secs;
+
+    for (mut transform, mut velocity, mut acceleration, mut state, flight_state, energy, startle, traits) in query.iter_mut() {
+        // Vitesses propres au boid, selon ses traits individuels
+        let speed_factor = traits.map_or(1.0, |traits| traits.speed);
+        // Un boid posé ne bouge pas ; en approche, il peut ralentir sous `min_speed`
+        let min_speed = match flight_state {
+            FlightState::Perched { .. } => {
+                acceleration.acceleration = Vec3::ZERO;
+                continue;
+            }
+            FlightState::Landing { .. } => 0.0,
+            _ => boid_settings.min_speed * speed_factor,
+        };
+        let max_speed = boid_settings.max_speed * speed_factor;
+        let max_speed = match energy {
+            Some(energy) if energy_settings.enabled => {
+                (max_speed * energy_settings.exhausted_speed_factor.lerp(1.0, energy.value)).max(min_speed)
+            }
+            _ => max_speed,
+        };
+        // Un boid effarouché dépasse brièvement sa vitesse de croisière
+        let max_speed = match startle {
+            Some(Startle::Escaping { .. }) if startle_settings.enabled => max_speed * startle_settings.escape_speed_factor,
+            _ => max_speed,
+        };
+
+        let limits = SpeedLimits { min_speed, max_speed };
+        let current = Kinematics {
+            position: transform.translation,
+            velocity: velocity.velocity,
+            acceleration: acceleration.acceleration,
+        };
+        let (position, new_velocity) = match step.phase {
+            StepPhase::Single => integrate(
+                integration_settings.integrator,
+                current,
+                state.previous_acceleration,
+                limits,
+                delta_secs,
+            ),
+            StepPhase::Predict => {
+                state.start_position = current.position;
+                state.start_velocity = current.velocity;
+                state.start_acceleration = current.acceleration;
+                integrate(Integrator::ExplicitEuler, current, Vec3::ZERO, limits, delta_secs)
+            }
+            StepPhase::Correct => {
+                let start = Kinematics {
+                    position: state.start_position,
+                    velocity: state.start_velocity,
+                    acceleration: state.start_acceleration,
+                };
+                rk2_correct(start, current, limits, delta_secs)
+            }
+        };
+
+        // Le virage de la vitesse elle-même est borné, pas seulement celui du maillage
+        let new_velocity = if orientation_settings.max_turn_rate > 0.0 {
+            let reference = match step.phase {
+                StepPhase::Correct => state.start_velocity,
+                _ => velocity.velocity,
+            };
+            limit_turn(reference, new_velocity, orientation_settings.max_turn_rate.to_radians() * delta_secs)
+        } else {
+            new_velocity
+        };
+
+        transform.translation = position;
+        velocity.velocity = new_velocity;
+
+        if step.is_final() {
+            // Orienter le boid dans la direction du mouvement
+            transform.rotation = orient_boid(
+                transform.rotation,
+                velocity.velocity,
+                acceleration.acceleration,
+                &orientation_settings,
+                delta_secs,
+            );
+            state.previous_acceleration = acceleration.acceleration;
+        }
+
+        // Réinitialiser l'accélération
+        acceleration.acceleration = Vec3::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: SpeedLimits = SpeedLimits { min_speed: 0.0, max_speed: 1000.0 };
+
+    #[test]
+    fn substep_count_splits_long_frames() {
+        let settings = IntegrationSettings { max_substep: 0.01, max_substeps: 8, ..default() };
+        assert_eq!(substep_count(0.005, &settings), 1);
+        assert_eq!(substep_count(0.01, &settings), 1);
+        assert_eq!(substep_count(0.025, &settings), 3);
+        // Une frame très longue est bornée par `max_substeps`
+        assert_eq!(substep_count(1.0, &settings), 8);
+    }
+
+    #[test]
+    fn substep_count_without_limit_is_one() {
+        let settings = IntegrationSettings { max_substep: 0.0, max_substeps: 8, ..default() };
+        assert_eq!(substep_count(1.0, &settings), 1);
+        let settings = IntegrationSettings { max_substep: 0.01, max_substeps: 0, ..default() };
+        assert_eq!(substep_count(1.0, &settings), 1);
+    }
+
+    #[test]
+    fn rk2_correct_averages_both_slopes() {
+        let start = Kinematics { position: Vec3::ZERO, velocity: Vec3::X * 2.0, acceleration: Vec3::Y * 4.0 };
+        let predicted = Kinematics { position: Vec3::splat(99.0), velocity: Vec3::X * 4.0, acceleration: Vec3::Y * 8.0 };
+        let (position, velocity) = rk2_correct(start, predicted, LIMITS, 0.5);
+        assert!((position - Vec3::X * 1.5).length() < 1e-5);
+        assert!((velocity - Vec3::new(2.0, 3.0, 0.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn rk2_correct_clamps_speed() {
+        let start = Kinematics { position: Vec3::ZERO, velocity: Vec3::X * 10.0, acceleration: Vec3::X * 100.0 };
+        let limits = SpeedLimits { min_speed: 0.0, max_speed: 12.0 };
+        let (_, velocity) = rk2_correct(start, start, limits, 1.0);
+        assert!((velocity.length() - 12.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn rk2_matches_constant_acceleration_exactly() {
+        // À accélération constante, Heun donne la position exacte x = v t + a t² / 2
+        let start = Kinematics { position: Vec3::ZERO, velocity: Vec3::X, acceleration: Vec3::Y * 2.0 };
+        let (_, predicted_velocity) = integrate(Integrator::ExplicitEuler, start, Vec3::ZERO, LIMITS, 1.0);
+        let predicted = Kinematics { velocity: predicted_velocity, ..start };
+        let (position, _) = rk2_correct(start, predicted, LIMITS, 1.0);
+        assert!((position - Vec3::new(1.0, 1.0, 0.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn limit_turn_keeps_small_turns() {
+        let next = Vec3::new(10.0, 1.0, 0.0);
+        assert_eq!(limit_turn(Vec3::X * 10.0, next, 0.5), next);
+    }
+
+    #[test]
+    fn limit_turn_clamps_angle_and_keeps_speed() {
+        let limited = limit_turn(Vec3::X * 10.0, Vec3::Z * 20.0, 0.1);
+        assert!((limited.length() - 20.0).abs() < 1e-4);
+        assert!((Vec3::X.angle_between(limited) - 0.1).abs() < 1e-4);
+        // Le virage se fait dans le plan des deux vitesses
+        assert!(limited.y.abs() < 1e-4 && limited.z > 0.0);
+    }
+
+    #[test]
+    fn limit_turn_handles_reversal_and_zero_velocity() {
+        let reversed = limit_turn(Vec3::X, -Vec3::X, 0.2);
+        assert!((Vec3::X.angle_between(reversed) - 0.2).abs() < 1e-4);
+        assert_eq!(limit_turn(Vec3::ZERO, Vec3::Y, 0.2), Vec3::Y);
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+use crate::components::boid::{Boid, FlightState, Velocity};
+use crate::resources::leaders::GroupLeaders;
+use crate::resources::settings::{GroupsTargets, LeaderSelection, LeaderSettings};
+
+const LEADER_COLOR: Color = Color::srgb(1.0, 0.6, 0.1);
+const LEADER_MARKER_RADIUS: f32 = 2.0;
+
+pub fn leaders_enabled(leader_settings: Res<LeaderSettings>) -> bool {
+    leader_settings.enabled
+}
+
+/// Élit les meneurs manquants et relaie les mandats arrivés à échéance.
+pub fn elect_leaders(
+    boid_query: Query<(Entity, &Transform, &Velocity, &Boid, &FlightState)>,
+    leader_settings: Res<LeaderSettings>,
+    groups_targets: Res<GroupsTargets>,
+    mut group_leaders: ResMut<GroupLeaders>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    let rotate = leader_settings.rotation_period > 0.0 && now >= group_leaders.next_rotation;
+    if rotate {
+        group_leaders.next_rotation = now + leader_settings.rotation_period;
+    }
+
+    let group_count = groups_targets.targets.len();
+    group_leaders.groups.resize(group_count, Vec::new());
+
+    for (group, target) in groups_targets.targets.iter().enumerate() {
+        // Membres en vol, dans l'ordre des entités pour un relais stable
+        let mut members: Vec<(Entity, Vec3, Vec3)> = boid_query
+            .iter()
+            .filter(|(_, _, _, boid, flight_state)| boid.group as usize == group && flight_state.is_airborne())
+            .map(|(entity, transform, velocity, _, _)| (entity, transform.translation, velocity.velocity))
+            .collect();
+        members.sort_by_key(|(entity, _, _)| *entity);
+
+        let count = leader_settings.leaders_per_group.min(members.len());
+        let leaders = &mut group_leaders.groups[group];
+        leaders.retain(|leader| members.iter().any(|(entity, _, _)| entity == leader));
+        leaders.truncate(count);
+        if !rotate && leaders.len() == count {
+            continue;
+        }
+
+        match leader_settings.selection {
+            LeaderSelection::Designated => {
+                // Au relais, le mandat passe aux boids qui suivent le dernier meneur
+                let start = if rotate {
+                    leaders
+                        .iter()
+                        .filter_map(|leader| members.iter().position(|(entity, _, _)| entity == leader))
+                        .max()
+                        .map_or(0, |last| last + 1)
+                } else {
+                    0
+                };
+                if rotate {
+                    leaders.clear();
+                }
+                for offset in 0..members.len() {
+                    if leaders.len() == count {
+                        break;
+                    }
+                    let (entity, _, _) = members[(start + offset) % members.len()];
+                    if !leaders.contains(&entity) {
+                        leaders.push(entity);
+                    }
+                }
+            }
+            LeaderSelection::FrontMost => {
+                // Les plus avancés le long de l'axe centre du groupe → cible
+                let center = members.iter().map(|(_, position, _)| *position).sum::<Vec3>() / members.len().max(1) as f32;
+                let heading = (*target - center).normalize_or_zero();
+                members.sort_by(|(_, a, _), (_, b, _)| b.dot(heading).total_cmp(&a.dot(heading)));
+                *leaders = members.iter().take(count).map(|(entity, _, _)| *entity).collect();
+            }
+        }
+    }
+}
+
+pub fn draw_leaders(
+    mut gizmos: Gizmos,
+    boid_query: Query<(&Transform, &Boid)>,
+    leader_settings: Res<LeaderSettings>,
+    group_leaders: Res<GroupLeaders>,
+    groups_targets: Res<GroupsTargets>,
+) {
+    if !leader_settings.show_leaders {
+        return;
+    }
+
+    for leader in group_leaders.groups.iter().flatten() {
+        let Ok((transform, boid)) = boid_query.get(*leader) else { continue; };
+        gizmos.sphere(transform.translation, LEADER_MARKER_RADIUS, LEADER_COLOR);
+        if let Some(target) = groups_targets.targets.get(boid.group as usize) {
+            gizmos.line(transform.translation, *target, LEADER_COLOR.with_alpha(0.3));
+        }
+    }
+}
@@ -1,3 +1,17 @@
 pub mod boids;
+pub mod camera;
+pub mod collision;
+pub mod debug;
+pub mod energy;
+pub mod flock_core;
+pub mod formation;
+pub mod inspection;
+pub mod integration;
+pub mod leaders;
+pub mod perching;
+pub mod picking;
+pub mod rendering;
 pub mod setup;
+pub mod startle;
+pub mod terrain;
 pub mod flocking;
\ No newline at end of file
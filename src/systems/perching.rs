@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::components::boid::{Acceleration, Boid, FlightState, Obstacle, Velocity};
+use crate::events::StartlePerchedEvent;
+use crate::resources::perch::PerchLines;
+use crate::resources::settings::{BoidSettings, CollisionSettings, PerchSettings};
+use crate::resources::terrain::TerrainHeightmap;
+
+// Distance au point de perche en deçà de laquelle le boid est posé
+const PERCH_REACHED_DISTANCE: f32 = 0.5;
+// Raideur du freinage à l'approche
+const LANDING_RESPONSE: f32 = 4.0;
+const PERCH_LINE_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
+
+pub fn perching_enabled(perch_settings: Res<PerchSettings>) -> bool {
+    perch_settings.enabled
+}
+
+/// Point de perche le plus proche parmi les surfaces autorisées.
+fn choose_perch(
+    position: Vec3,
+    perch_settings: &PerchSettings,
+    perch_lines: &PerchLines,
+    heightmap: &TerrainHeightmap,
+    obstacles: &[(Vec3, f32)],
+    contact_radius: f32,
+) -> Option<Vec3> {
+    let terrain = perch_settings
+        .perch_on_terrain
+        .then(|| Vec3::new(position.x, heightmap.height_at(position.x, position.z) + contact_radius, position.z));
+    let obstacle = perch_settings
+        .perch_on_obstacles
+        .then(|| {
+            obstacles
+                .iter()
+                .map(|(center, radius)| *center + Vec3::Y * (radius + contact_radius))
+                .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)))
+        })
+        .flatten();
+    let line = perch_settings
+        .perch_on_lines
+        .then(|| perch_lines.closest_point(position))
+        .flatten();
+
+    [terrain, obstacle, line]
+        .into_iter()
+        .flatten()
+        .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)))
+}
+
+pub fn decide_landing(
+    mut boid_query: Query<(&Transform, &mut FlightState), With<Boid>>,
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Boid>>,
+    perch_settings: Res<PerchSettings>,
+    perch_lines: Res<PerchLines>,
+    heightmap: Res<TerrainHeightmap>,
+    collision_settings: Res<CollisionSettings>,
+    time: Res<Time>,
+) {
+    let mut rng = rand::rng();
+    let chance = perch_settings.landing_rate * time.delta_secs();
+    let obstacles: Vec<(Vec3, f32)> = obstacle_query
+        .iter()
+        .map(|(transform, obstacle)| (transform.translation, obstacle.radius))
+        .collect();
+
+    for (transform, mut flight_state) in boid_query.iter_mut() {
+        if *flight_state != FlightState::Flying || rng.random::<f32>() >= chance {
+            continue;
+        }
+
+        if let Some(perch) = choose_perch(
+            transform.translation,
+            &perch_settings,
+            &perch_lines,
+            &heightmap,
+            &obstacles,
+            collision_settings.boid_radius,
+        ) {
+            *flight_state = FlightState::Landing { perch };
+        }
+    }
+}
+
+/// Sans perchage, les boids en approche repartent et les boids posés s'envolent.
+pub fn cancel_perching(mut boid_query: Query<&mut FlightState, With<Boid>>, time: Res<Time>) {
+    for mut flight_state in boid_query.iter_mut() {
+        match *flight_state {
+            FlightState::Landing { .. } => *flight_state = FlightState::Flying,
+            FlightState::Perched { until } if until > time.elapsed_secs() => {
+                *flight_state = FlightState::Perched { until: time.elapsed_secs() };
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Remplace l'accélération des boids en approche par un freinage vers la perche.
+pub fn steer_landing(
+    mut boid_query: Query<(&Transform, &Velocity, &FlightState, &mut Acceleration), With<Boid>>,
+    perch_settings: Res<PerchSettings>,
+) {
+    for (transform, velocity, flight_state, mut acceleration) in boid_query.iter_mut() {
+        let FlightState::Landing { perch } = flight_state else {
+            continue;
+        };
+
+        let to_perch = *perch - transform.translation;
+        let distance = to_perch.length();
+        let speed = perch_settings.landing_speed.min(distance * LANDING_RESPONSE);
+        let desired = to_perch.normalize_or_zero() * speed;
+        acceleration.acceleration = (desired - velocity.velocity) * LANDING_RESPONSE;
+    }
+}
+
+pub fn update_flight_states(
+    mut boid_query: Query<(&mut Transform, &mut Velocity, &mut FlightState), With<Boid>>,
+    perch_settings: Res<PerchSettings>,
+    boid_settings: Res<BoidSettings>,
+    time: Res<Time>,
+) {
+    let mut rng = rand::rng();
+    let now = time.elapsed_secs();
+    let mut took_off = Vec::new();
+
+    for (mut transform, mut velocity, mut flight_state) in boid_query.iter_mut() {
+        match *flight_state {
+            FlightState::Flying => {}
+            FlightState::Landing { perch } => {
+                if transform.translation.distance(perch) < PERCH_REACHED_DISTANCE {
+                    transform.translation = perch;
+                    velocity.velocity = Vec3::ZERO;
+                    let rest = rng.random_range(perch_settings.min_rest..=perch_settings.max_rest.max(perch_settings.min_rest));
+                    *flight_state = FlightState::Perched { until: now + rest };
+                }
+            }
+            FlightState::Perched { until } => {
+                if now >= until {
+                    // Envol vers le haut dans une direction horizontale aléatoire
+                    let heading = rng.random_range(0.0..std::f32::consts::TAU);
+                    let horizontal = Vec3::new(heading.cos(), 0.0, heading.sin());
+                    velocity.velocity = (horizontal * 0.7 + Vec3::Y).normalize() * boid_settings.min_speed.max(1.0);
+                    *flight_state = FlightState::TakingOff { until: now + perch_settings.takeoff_duration };
+                    took_off.push(transform.translation);
+                }
+            }
+            FlightState::TakingOff { until } => {
+                if now >= until {
+                    *flight_state = FlightState::Flying;
+                }
+            }
+        }
+    }
+
+    if !perch_settings.startle_cascade || took_off.is_empty() {
+        return;
+    }
+
+    // Les voisins posés d'un boid qui s'envole s'envolent à leur tour, avec un léger retard
+    let radius_squared = perch_settings.cascade_radius * perch_settings.cascade_radius;
+    for (transform, _, mut flight_state) in boid_query.iter_mut() {
+        let FlightState::Perched { until } = *flight_state else {
+            continue;
+        };
+
+        let startled = took_off
+            .iter()
+            .any(|position| position.distance_squared(transform.translation) < radius_squared);
+        if startled {
+            let reaction = now + rng.random_range(0.0..=perch_settings.cascade_delay.max(0.0));
+            *flight_state = FlightState::Perched { until: until.min(reaction) };
+        }
+    }
+}
+
+/// Fait s'envoler un boid posé au hasard ; la cascade se charge des autres.
+pub fn startle_perched(
+    mut events: EventReader<StartlePerchedEvent>,
+    mut boid_query: Query<&mut FlightState, With<Boid>>,
+    time: Res<Time>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+
+    let mut perched: Vec<_> = boid_query
+        .iter_mut()
+        .filter(|flight_state| matches!(**flight_state, FlightState::Perched { .. }))
+        .collect();
+    if perched.is_empty() {
+        return;
+    }
+
+    let index = rand::rng().random_range(0..perched.len());
+    *perched[index] = FlightState::Perched { until: time.elapsed_secs() };
+}
+
+pub fn draw_perch_lines(mut gizmos: Gizmos, perch_settings: Res<PerchSettings>, perch_lines: Res<PerchLines>) {
+    if !perch_settings.perch_on_lines {
+        return;
+    }
+
+    for (start, end) in &perch_lines.lines {
+        gizmos.line(*start, *end, PERCH_LINE_COLOR);
+    }
+}
@@ -0,0 +1,54 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+// Tolérance autour du centre d'un boid pour le sélectionner au clic
+pub const PICK_RADIUS: f32 = 3.0;
+
+/// Fenêtre et caméra principales, pour lancer un rayon sous le curseur.
+#[derive(SystemParam)]
+pub struct CursorView<'w> {
+    window: Single<'w, &'static Window, With<PrimaryWindow>>,
+    camera: Single<'w, (&'static Camera, &'static GlobalTransform), With<Camera3d>>,
+}
+
+impl CursorView<'_> {
+    pub fn ray(&self) -> Option<Ray3d> {
+        let (camera, camera_transform) = *self.camera;
+        cursor_ray(&self.window, camera, camera_transform)
+    }
+}
+
+pub fn cursor_ray(window: &Window, camera: &Camera, camera_transform: &GlobalTransform) -> Option<Ray3d> {
+    let cursor = window.cursor_position()?;
+    camera.viewport_to_world(camera_transform, cursor).ok()
+}
+
+// Boid le plus proche de la caméra dont le centre passe à moins de `radius` du rayon
+pub fn pick_boid(ray: Ray3d, boids: impl Iterator<Item = (Entity, Vec3)>, radius: f32) -> Option<(Entity, Vec3)> {
+    boids
+        .filter_map(|(entity, position)| {
+            let along = (position - ray.origin).dot(*ray.direction);
+            if along <= 0.0 {
+                return None;
+            }
+            let distance_squared = ray.get_point(along).distance_squared(position);
+            (distance_squared <= radius * radius).then_some((along, entity, position))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, entity, position)| (entity, position))
+}
+
+// Distance le long du rayon jusqu'à la première intersection avec la sphère
+pub fn ray_sphere_distance(ray: Ray3d, center: Vec3, radius: f32) -> Option<f32> {
+    let to_center = center - ray.origin;
+    let along = to_center.dot(*ray.direction);
+    let distance_squared = to_center.length_squared() - along * along;
+    let half_chord_squared = radius * radius - distance_squared;
+    if half_chord_squared < 0.0 {
+        return None;
+    }
+
+    let half_chord = half_chord_squared.sqrt();
+    [along - half_chord, along + half_chord].into_iter().find(|distance| *distance > 0.0)
+}
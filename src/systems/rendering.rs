@@ -0,0 +1,417 @@
+use bevy::gltf::{Gltf, GltfMesh, GltfNode};
+use bevy::prelude::*;
+use bevy::render::mesh::{PrimitiveTopology, VertexAttributeValues};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::scene::SceneInstance;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::TAU;
+use crate::components::boid::{Boid, BoidVisual, RenderedAs, Velocity, Wing, WingFlap};
+use crate::resources::rendering::{ArticulatedBird, BirdAssets};
+use crate::resources::settings::{BoidRenderMode, BoidSettings, LodSettings, WingSettings};
+
+// Taille des cellules de regroupement du maillage simplifié, dans le repère du modèle
+const LOW_POLY_CELL_SIZE: f32 = 4.0;
+// Part de la demi-envergure occupée par le corps
+const WING_ROOT_FRACTION: f32 = 0.4;
+
+pub fn build_bird_meshes(
+    mut bird_assets: ResMut<BirdAssets>,
+    gltfs: Res<Assets<Gltf>>,
+    gltf_nodes: Res<Assets<GltfNode>>,
+    gltf_meshes: Res<Assets<GltfMesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    if bird_assets.instanced_mesh.is_some() {
+        return;
+    }
+    let Some(gltf) = gltfs.get(&bird_assets.gltf) else {
+        return;
+    };
+
+    // Toutes les primitives sont ramenées dans le repère de la scène puis fusionnées
+    let mut merged: Option<Mesh> = None;
+    for node in gltf.nodes.iter().filter_map(|node| gltf_nodes.get(node)) {
+        let Some(gltf_mesh) = node.mesh.as_ref().and_then(|mesh| gltf_meshes.get(mesh)) else {
+            continue;
+        };
+
+        for primitive in &gltf_mesh.primitives {
+            let Some(mesh) = meshes.get(&primitive.mesh) else {
+                continue;
+            };
+            let mesh = mesh.clone().transformed_by(node.transform);
+
+            match merged.as_mut() {
+                Some(merged) => {
+                    if let Err(error) = merged.merge(&mesh) {
+                        warn!("Primitive {} ignorée : {}", primitive.name, error);
+                    }
+                }
+                None => merged = Some(mesh),
+            }
+        }
+    }
+
+    if let Some(mesh) = merged {
+        bird_assets.low_poly_mesh = simplify_mesh(&mesh, LOW_POLY_CELL_SIZE).map(|low_poly| meshes.add(low_poly));
+        bird_assets.articulated = split_wings(&mesh).map(|parts| ArticulatedBird {
+            body: meshes.add(parts.body),
+            wings: parts.wings.map(|(wing, pivot)| (meshes.add(wing), pivot)),
+            hinge_axis: parts.hinge_axis,
+        });
+        bird_assets.instanced_mesh = Some(meshes.add(mesh));
+    }
+}
+
+struct BirdParts {
+    body: Mesh,
+    wings: [(Mesh, Vec3); 2],
+    hinge_axis: Vec3,
+}
+
+#[derive(Default)]
+struct MeshPart {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+}
+
+// Sépare les triangles du corps de ceux des ailes. L'envergure est l'axe le plus
+// long et le plus symétrique autour de l'origine du modèle.
+fn split_wings(mesh: &Mesh) -> Option<BirdParts> {
+    let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+        return None;
+    };
+    let normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float32x3(normals)) => Some(normals),
+        _ => None,
+    };
+    let indices: Vec<usize> = mesh.indices()?.iter().collect();
+    let positions: Vec<Vec3> = positions.iter().map(|position| Vec3::from(*position)).collect();
+
+    let (min, max) = positions
+        .iter()
+        .fold((Vec3::MAX, Vec3::MIN), |(min, max), position| (min.min(*position), max.max(*position)));
+    let extent = max - min;
+    let symmetry = extent - (min + max).abs();
+
+    let span_axis = (0..3).max_by(|a, b| symmetry[*a].total_cmp(&symmetry[*b]))?;
+    let forward_axis = (0..3)
+        .filter(|axis| *axis != span_axis)
+        .max_by(|a, b| extent[*a].total_cmp(&extent[*b]))?;
+    let hinge = extent[span_axis] * 0.5 * WING_ROOT_FRACTION;
+    let pivots = [1.0, -1.0].map(|side| Vec3::AXES[span_axis] * side * hinge);
+
+    // Corps, aile du côté +1, aile du côté -1
+    let mut parts: [MeshPart; 3] = Default::default();
+    for triangle in indices.chunks_exact(3) {
+        let centroid = (positions[triangle[0]] + positions[triangle[1]] + positions[triangle[2]]) / 3.0;
+        let (part, offset) = if centroid[span_axis] > hinge {
+            (1, pivots[0])
+        } else if centroid[span_axis] < -hinge {
+            (2, pivots[1])
+        } else {
+            (0, Vec3::ZERO)
+        };
+
+        for &index in triangle {
+            parts[part].positions.push((positions[index] - offset).to_array());
+            if let Some(normals) = normals {
+                parts[part].normals.push(normals[index]);
+            }
+        }
+    }
+
+    if parts.iter().any(|part| part.positions.is_empty()) {
+        return None;
+    }
+
+    let [body, left, right] = parts.map(|part| {
+        let mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, part.positions);
+        if part.normals.is_empty() {
+            mesh.with_computed_flat_normals()
+        } else {
+            mesh.with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, part.normals)
+        }
+    });
+
+    Some(BirdParts {
+        body,
+        wings: [(left, pivots[0]), (right, pivots[1])],
+        hinge_axis: Vec3::AXES[forward_axis],
+    })
+}
+
+// Simplification par regroupement de sommets : chaque cellule de la grille
+// devient un sommet unique et les triangles dégénérés disparaissent.
+fn simplify_mesh(mesh: &Mesh, cell_size: f32) -> Option<Mesh> {
+    let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+        return None;
+    };
+    let indices: Vec<usize> = mesh.indices()?.iter().collect();
+
+    let mut clusters: HashMap<IVec3, usize> = HashMap::new();
+    let mut sums: Vec<(Vec3, f32)> = Vec::new();
+    let remap: Vec<usize> = positions
+        .iter()
+        .map(|position| {
+            let position = Vec3::from(*position);
+            let cell = (position / cell_size).floor().as_ivec3();
+            let cluster = *clusters.entry(cell).or_insert_with(|| {
+                sums.push((Vec3::ZERO, 0.0));
+                sums.len() - 1
+            });
+            sums[cluster].0 += position;
+            sums[cluster].1 += 1.0;
+            cluster
+        })
+        .collect();
+    let centers: Vec<Vec3> = sums.iter().map(|(sum, count)| *sum / *count).collect();
+
+    let mut seen = HashSet::new();
+    let mut triangles: Vec<[f32; 3]> = Vec::new();
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [remap[triangle[0]], remap[triangle[1]], remap[triangle[2]]];
+        if a == b || b == c || a == c {
+            continue;
+        }
+
+        let mut key = [a, b, c];
+        key.sort_unstable();
+        if !seen.insert(key) {
+            continue;
+        }
+
+        triangles.extend([centers[a], centers[b], centers[c]].map(|vertex| vertex.to_array()));
+    }
+
+    if triangles.is_empty() {
+        return None;
+    }
+
+    Some(
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, triangles)
+            .with_computed_flat_normals(),
+    )
+}
+
+// Rang de détail d'un niveau de LOD, du plus détaillé au plus simple
+fn lod_rank(visual: BoidVisual) -> usize {
+    match visual {
+        BoidVisual::Scene | BoidVisual::Instanced | BoidVisual::Animated => 0,
+        BoidVisual::LowPoly => 1,
+        BoidVisual::Point => 2,
+    }
+}
+
+/// Niveau de LOD voulu. Un boid ne franchit un seuil qu'une fois sorti de la bande
+/// d'hystérésis qui l'entoure, pour ne pas clignoter d'un maillage à l'autre.
+fn desired_visual(
+    render_mode: BoidRenderMode,
+    camera_distance: f32,
+    current: Option<BoidVisual>,
+    lod_settings: &LodSettings,
+) -> BoidVisual {
+    match render_mode {
+        BoidRenderMode::Scene => BoidVisual::Scene,
+        BoidRenderMode::Instanced => BoidVisual::Instanced,
+        BoidRenderMode::Animated => BoidVisual::Animated,
+        BoidRenderMode::Lod => {
+            let half_band = lod_settings.hysteresis.max(0.0) * 0.5;
+            let rank = [lod_settings.near_distance, lod_settings.far_distance]
+                .iter()
+                .enumerate()
+                .filter(|(boundary, threshold)| {
+                    // Le seuil recule devant le niveau actuel de part et d'autre
+                    let threshold = match current.map(lod_rank) {
+                        Some(rank) if rank <= *boundary => **threshold + half_band,
+                        Some(_) => **threshold - half_band,
+                        None => **threshold,
+                    };
+                    camera_distance >= threshold
+                })
+                .count();
+            [BoidVisual::Animated, BoidVisual::LowPoly, BoidVisual::Point][rank]
+        }
+    }
+}
+
+pub fn sync_boid_rendering(
+    mut commands: Commands,
+    boid_settings: Res<BoidSettings>,
+    lod_settings: Res<LodSettings>,
+    bird_assets: Res<BirdAssets>,
+    camera: Option<Single<&GlobalTransform, With<Camera3d>>>,
+    boid_query: Query<(Entity, &Boid, &Transform, Option<&RenderedAs>)>,
+) {
+    let camera_position = camera.map(|camera| camera.translation()).unwrap_or_default();
+
+    for (entity, boid, transform, rendered_as) in boid_query.iter() {
+        let camera_distance = transform.translation.distance(camera_position);
+        let current = rendered_as.map(|rendered_as| rendered_as.0);
+        let mut visual = desired_visual(boid_settings.render_mode, camera_distance, current, &lod_settings);
+
+        // Un maillage dérivé impossible à construire est remplacé par le maillage fusionné
+        let derived_missing = match visual {
+            BoidVisual::Animated => bird_assets.articulated.is_none(),
+            BoidVisual::LowPoly => bird_assets.low_poly_mesh.is_none(),
+            _ => false,
+        };
+        if derived_missing && bird_assets.instanced_mesh.is_some() {
+            visual = BoidVisual::Instanced;
+        }
+
+        if current == Some(visual) {
+            continue;
+        }
+
+        // Attendre que le glTF soit chargé pour les maillages dérivés
+        let mesh_and_material = match visual {
+            BoidVisual::Scene => None,
+            BoidVisual::Animated => bird_assets
+                .articulated
+                .as_ref()
+                .map(|articulated| (articulated.body.clone(), bird_assets.group_material(boid.group))),
+            BoidVisual::Instanced => bird_assets
+                .instanced_mesh
+                .clone()
+                .map(|mesh| (mesh, bird_assets.group_material(boid.group))),
+            BoidVisual::LowPoly => bird_assets
+                .low_poly_mesh
+                .clone()
+                .map(|mesh| (mesh, bird_assets.group_material(boid.group))),
+            BoidVisual::Point => Some((bird_assets.point_mesh.clone(), bird_assets.point_material(boid.group))),
+        };
+        if visual != BoidVisual::Scene && mesh_and_material.is_none() {
+            continue;
+        }
+
+        let mut entity_commands = commands.entity(entity);
+        if let Some(RenderedAs(previous @ (BoidVisual::Scene | BoidVisual::Animated))) = rendered_as {
+            if *previous == BoidVisual::Scene {
+                entity_commands.remove::<(SceneRoot, SceneInstance)>();
+            }
+            entity_commands.despawn_related::<Children>();
+        }
+
+        match mesh_and_material {
+            Some((mesh, material)) => {
+                entity_commands.insert((Mesh3d(mesh), MeshMaterial3d(material)));
+            }
+            None => {
+                entity_commands
+                    .remove::<(Mesh3d, MeshMaterial3d<StandardMaterial>)>()
+                    .insert(SceneRoot(bird_assets.scene.clone()));
+            }
+        }
+
+        if let (BoidVisual::Animated, Some(articulated)) = (visual, &bird_assets.articulated) {
+            let material = bird_assets.group_material(boid.group);
+            entity_commands.with_children(|parent| {
+                for ((mesh, pivot), side) in articulated.wings.iter().zip([1.0, -1.0]) {
+                    parent.spawn((
+                        Wing { side },
+                        Mesh3d(mesh.clone()),
+                        MeshMaterial3d(material.clone()),
+                        Transform::from_translation(*pivot),
+                    ));
+                }
+            });
+        }
+
+        entity_commands.insert(RenderedAs(visual));
+    }
+}
+
+pub fn update_wing_flap(
+    mut query: Query<(&Velocity, &mut WingFlap)>,
+    boid_settings: Res<BoidSettings>,
+    wing_settings: Res<WingSettings>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_secs();
+    if delta_secs <= 0.0 {
+        return;
+    }
+
+    query.par_iter_mut().for_each(|(velocity, mut wing_flap)| {
+        let speed = velocity.velocity.length();
+        let acceleration = (velocity.velocity - wing_flap.previous_velocity) / delta_secs;
+        let speed_gain = (speed - wing_flap.previous_velocity.length()) / delta_secs;
+        let climb = acceleration.y.max(0.0);
+
+        // On bat des ailes pour accélérer ou monter, on plane sinon
+        let effort = speed_gain.max(0.0) + climb;
+        let target_amplitude = if effort > wing_settings.glide_threshold { 1.0 } else { 0.0 };
+        wing_flap.amplitude += (target_amplitude - wing_flap.amplitude) * (1.0 - (-4.0 * delta_secs).exp());
+
+        let speed_range = (boid_settings.max_speed - boid_settings.min_speed).max(f32::EPSILON);
+        let speed_ratio = ((speed - boid_settings.min_speed) / speed_range).clamp(0.0, 1.0);
+        let frequency = wing_settings.flap_frequency
+            + wing_settings.speed_frequency_gain * speed_ratio
+            + wing_settings.climb_frequency_gain * climb;
+
+        // Pendant le plané la phase continue lentement pour reprendre sans à-coup
+        wing_flap.phase = (wing_flap.phase + TAU * frequency * wing_flap.amplitude.max(0.1) * delta_secs) % TAU;
+        wing_flap.previous_velocity = velocity.velocity;
+    });
+}
+
+pub fn animate_wings(
+    mut wing_query: Query<(&Wing, &ChildOf, &mut Transform)>,
+    flap_query: Query<&WingFlap>,
+    bird_assets: Res<BirdAssets>,
+    wing_settings: Res<WingSettings>,
+) {
+    let Some(articulated) = &bird_assets.articulated else {
+        return;
+    };
+
+    for (wing, child_of, mut transform) in wing_query.iter_mut() {
+        let Ok(wing_flap) = flap_query.get(child_of.parent()) else {
+            continue;
+        };
+
+        let angle = wing_settings.glide_angle + wing_settings.flap_amplitude * wing_flap.amplitude * wing_flap.phase.sin();
+        transform.rotation = Quat::from_axis_angle(articulated.hinge_axis, wing.side * angle.to_radians());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lod_settings() -> LodSettings {
+        LodSettings {
+            near_distance: 100.0,
+            far_distance: 200.0,
+            hysteresis: 20.0,
+        }
+    }
+
+    #[test]
+    fn lod_levels_follow_thresholds_without_current_visual() {
+        let settings = lod_settings();
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 50.0, None, &settings), BoidVisual::Animated);
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 150.0, None, &settings), BoidVisual::LowPoly);
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 250.0, None, &settings), BoidVisual::Point);
+    }
+
+    #[test]
+    fn lod_keeps_current_level_inside_hysteresis_band() {
+        let settings = lod_settings();
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 105.0, Some(BoidVisual::Animated), &settings), BoidVisual::Animated);
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 95.0, Some(BoidVisual::LowPoly), &settings), BoidVisual::LowPoly);
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 195.0, Some(BoidVisual::Point), &settings), BoidVisual::Point);
+    }
+
+    #[test]
+    fn lod_switches_once_outside_hysteresis_band() {
+        let settings = lod_settings();
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 111.0, Some(BoidVisual::Animated), &settings), BoidVisual::LowPoly);
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 89.0, Some(BoidVisual::LowPoly), &settings), BoidVisual::Animated);
+        // Le maillage fusionné de repli compte comme le niveau détaillé
+        assert_eq!(desired_visual(BoidRenderMode::Lod, 105.0, Some(BoidVisual::Instanced), &settings), BoidVisual::Animated);
+    }
+}
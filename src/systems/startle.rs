@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::input::EguiWantsInput;
+use bevy_spatial::SpatialAccess;
+use rand::Rng;
+use crate::components::boid::{Boid, FlightState, Startle};
+use crate::components::spatial::NNTree3D;
+use crate::events::StartleEvent;
+use crate::resources::settings::StartleSettings;
+use crate::resources::startle::StartleWaves;
+use crate::systems::picking::{cursor_ray, pick_boid, PICK_RADIUS};
+
+const ESCAPING_COLOR: Color = Color::srgb(1.0, 0.4, 0.0);
+const PENDING_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+const AGITATION_RADIUS: f32 = 1.5;
+// Durée d'affichage du front d'une vague (s)
+const WAVE_DISPLAY_TIME: f32 = 3.0;
+
+pub fn startle_enabled(startle_settings: Res<StartleSettings>) -> bool {
+    startle_settings.enabled
+}
+
+/// Clic droit sur un boid : il fuit à l'opposé de la caméra.
+pub fn startle_on_click(
+    mut events: EventWriter<StartleEvent>,
+    egui_wants_input: Res<EguiWantsInput>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera3d>>,
+    boid_query: Query<(Entity, &Transform), With<Boid>>,
+) {
+    if egui_wants_input.wants_any_pointer_input() || !mouse_buttons.just_pressed(MouseButton::Right) {
+        return;
+    }
+
+    let (camera, camera_transform) = *camera;
+    let Some(ray) = cursor_ray(&window, camera, camera_transform) else {
+        return;
+    };
+
+    let boids = boid_query.iter().map(|(entity, transform)| (entity, transform.translation));
+    if let Some((entity, _)) = pick_boid(ray, boids, PICK_RADIUS) {
+        events.write(StartleEvent { entity, threat: ray.origin });
+    }
+}
+
+/// Déclenchements spontanés, sur un boid au hasard et dans une direction au hasard.
+pub fn startle_randomly(
+    mut events: EventWriter<StartleEvent>,
+    boid_query: Query<(Entity, &Transform, &Startle), With<Boid>>,
+    startle_settings: Res<StartleSettings>,
+    time: Res<Time>,
+) {
+    let mut rng = rand::rng();
+    if rng.random::<f32>() >= startle_settings.random_rate * time.delta_secs() {
+        return;
+    }
+
+    let calm: Vec<_> = boid_query
+        .iter()
+        .filter(|(_, _, startle)| **startle == Startle::Calm)
+        .collect();
+    if calm.is_empty() {
+        return;
+    }
+
+    let (entity, transform, _) = calm[rng.random_range(0..calm.len())];
+    let direction = Vec3::new(rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0), rng.random_range(-1.0..1.0));
+    events.write(StartleEvent { entity, threat: transform.translation + direction });
+}
+
+/// La caméra joue le prédateur : les boids calmes trop proches s'en écartent.
+pub fn startle_near_camera(
+    mut events: EventWriter<StartleEvent>,
+    boid_query: Query<(Entity, &Transform, &Startle), With<Boid>>,
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+    startle_settings: Res<StartleSettings>,
+) {
+    if !startle_settings.camera_threat {
+        return;
+    }
+
+    let threat = camera.translation();
+    let radius_squared = startle_settings.camera_threat_radius * startle_settings.camera_threat_radius;
+    for (entity, transform, startle) in boid_query.iter() {
+        if *startle == Startle::Calm && transform.translation.distance_squared(threat) < radius_squared {
+            events.write(StartleEvent { entity, threat });
+        }
+    }
+}
+
+/// Fait avancer chaque réaction et propage les nouvelles fuites aux voisins calmes.
+pub fn propagate_startles(
+    mut events: EventReader<StartleEvent>,
+    mut boid_query: Query<(Entity, &Transform, &FlightState, &mut Startle), With<Boid>>,
+    mut waves: ResMut<StartleWaves>,
+    kd_tree: Res<NNTree3D>,
+    startle_settings: Res<StartleSettings>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_secs();
+    let escape_until = now + startle_settings.escape_duration;
+    // Boids qui se mettent à fuir pendant cette frame
+    let mut started: Vec<(Vec3, Vec3, u32)> = Vec::new();
+
+    for event in events.read() {
+        let Ok((_, transform, flight_state, mut startle)) = boid_query.get_mut(event.entity) else {
+            continue;
+        };
+        if *startle != Startle::Calm || !flight_state.is_airborne() {
+            continue;
+        }
+
+        let position = transform.translation;
+        let escape = (position - event.threat).try_normalize().unwrap_or(Vec3::Y);
+        let wave = waves.start_wave(position, now);
+        *startle = Startle::Escaping { until: escape_until, escape, wave };
+        started.push((position, escape, wave));
+    }
+
+    for (_, transform, flight_state, mut startle) in boid_query.iter_mut() {
+        match *startle {
+            Startle::Pending { at, escape, wave } if now >= at => {
+                // Un boid qui s'est posé entre-temps ne réagit plus
+                if !flight_state.is_airborne() {
+                    *startle = Startle::Calm;
+                    continue;
+                }
+                *startle = Startle::Escaping { until: escape_until, escape, wave };
+                waves.record_reaction(wave, transform.translation, now);
+                started.push((transform.translation, escape, wave));
+            }
+            Startle::Escaping { until, .. } if now >= until => {
+                *startle = Startle::Recovering { until: now + startle_settings.refractory };
+            }
+            Startle::Recovering { until } if now >= until => {
+                *startle = Startle::Calm;
+            }
+            _ => {}
+        }
+    }
+
+    // Chaque voisin calme copie la fuite après son temps de réaction
+    let mut rng = rand::rng();
+    for (position, escape, wave) in started {
+        for (_, neighbour) in kd_tree.within_distance(position, startle_settings.propagation_radius) {
+            let Some(neighbour) = neighbour else { continue; };
+            let Ok((_, _, flight_state, mut startle)) = boid_query.get_mut(neighbour) else { continue; };
+            if *startle != Startle::Calm || !flight_state.is_airborne() {
+                continue;
+            }
+
+            let jitter = rng.random_range(0.0..=startle_settings.latency_jitter.max(0.0));
+            *startle = Startle::Pending { at: now + startle_settings.latency + jitter, escape, wave };
+        }
+    }
+
+    waves.escaping = boid_query
+        .iter()
+        .filter_map(|(entity, _, _, startle)| match startle {
+            Startle::Escaping { escape, .. } => Some((entity, *escape)),
+            _ => None,
+        })
+        .collect();
+}
+
+/// Calme tous les boids quand l'effarouchement est désactivé.
+pub fn calm_startles(mut boid_query: Query<&mut Startle, With<Boid>>, mut waves: ResMut<StartleWaves>) {
+    for mut startle in boid_query.iter_mut() {
+        if *startle != Startle::Calm {
+            *startle = Startle::Calm;
+        }
+    }
+    waves.escaping.clear();
+}
+
+/// Boids en fuite ou sur le point de réagir, et front des vagues récentes.
+pub fn draw_startles(
+    mut gizmos: Gizmos,
+    boid_query: Query<(&Transform, &Startle), With<Boid>>,
+    waves: Res<StartleWaves>,
+    startle_settings: Res<StartleSettings>,
+    time: Res<Time>,
+) {
+    if !startle_settings.show_waves {
+        return;
+    }
+
+    for (transform, startle) in boid_query.iter() {
+        let color = match startle {
+            Startle::Escaping { .. } => ESCAPING_COLOR,
+            Startle::Pending { .. } => PENDING_COLOR,
+            _ => continue,
+        };
+        gizmos.sphere(transform.translation, AGITATION_RADIUS, color);
+    }
+
+    let now = time.elapsed_secs();
+    for wave in waves.waves.iter().filter(|wave| now - wave.started < WAVE_DISPLAY_TIME) {
+        let fade = 1.0 - (now - wave.started) / WAVE_DISPLAY_TIME;
+        gizmos.sphere(wave.origin, wave.front_distance.max(AGITATION_RADIUS), ESCAPING_COLOR.with_alpha(fade * 0.5));
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use crate::components::terrain::TerrainMesh;
+use crate::resources::settings::TerrainSettings;
+use crate::resources::terrain::{TerrainHeightmap, TERRAIN_RESOLUTION};
+
+fn build_terrain_mesh(heightmap: &TerrainHeightmap) -> Mesh {
+    let side = TERRAIN_RESOLUTION + 1;
+    let mut positions = Vec::with_capacity(side * side);
+    let mut uvs = Vec::with_capacity(side * side);
+
+    for z in 0..side {
+        for x in 0..side {
+            let position = TerrainHeightmap::grid_position(x, z);
+            positions.push([position.x, heightmap.vertex_height(x, z), position.y]);
+            uvs.push([x as f32 / TERRAIN_RESOLUTION as f32, z as f32 / TERRAIN_RESOLUTION as f32]);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(TERRAIN_RESOLUTION * TERRAIN_RESOLUTION * 6);
+    for z in 0..TERRAIN_RESOLUTION {
+        for x in 0..TERRAIN_RESOLUTION {
+            let corner = (z * side + x) as u32;
+            let next_row = corner + side as u32;
+            // Deux triangles par cellule, face vers le haut
+            indices.extend_from_slice(&[corner, next_row, corner + 1, corner + 1, next_row, next_row + 1]);
+        }
+    }
+
+    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+        .with_computed_smooth_normals()
+}
+
+pub fn rebuild_terrain(
+    mut commands: Commands,
+    mut heightmap: ResMut<TerrainHeightmap>,
+    terrain_settings: Res<TerrainSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    terrain_query: Query<Entity, With<TerrainMesh>>,
+) {
+    if heightmap.is_up_to_date(&terrain_settings) && !terrain_query.is_empty() {
+        return;
+    }
+
+    *heightmap = TerrainHeightmap::generate(&terrain_settings);
+
+    for entity in terrain_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    commands.spawn((
+        TerrainMesh,
+        Mesh3d(meshes.add(build_terrain_mesh(&heightmap))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgb(0.35, 0.45, 0.25),
+            perceptual_roughness: 1.0,
+            ..default()
+        })),
+        Transform::default(),
+    ));
+}
@@ -1,5 +1,23 @@
-use crate::resources::settings::BoidSettings;
+use crate::components::boid::FlightState;
+use crate::resources::camera::{CameraMode, CameraSettings, CameraState};
+use crate::events::{ResampleTraitsEvent, StartlePerchedEvent};
+use crate::resources::collision::CollisionStats;
+use crate::resources::energy::EnergyStats;
+use crate::resources::formation::FormationState;
+use crate::resources::inspection::SelectedBoid;
+use crate::resources::leaders::GroupLeaders;
+use crate::resources::startle::StartleWaves;
+use crate::resources::wind::WindField;
+use crate::resources::settings::{
+    BoidRenderMode, BoidSettings, CollisionResponse, CollisionSettings, DebugOverlaySettings, EnergySettings, ForceCombination, FormationSettings, FormationShape, IntegrationSettings, Integrator, LeaderSelection, LeaderSettings,
+    LodSettings, OrientationSettings, OverlayScope, PerchSettings, SimulationBackend, StartleSettings, TerrainSettings,
+    PerceptionSettings, TraitDistribution, TraitSettings,
+    WanderSettings, WingSettings,
+};
+use crate::steering::registry::{SteeringRegistry, BUILTIN_COUNT};
+use crate::systems::flock_core::{ECS_STEP_TIME, PACKED_STEP_TIME};
 use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::system::SystemParam;
 
 use bevy::prelude::*;
 use bevy_egui::{EguiContexts, EguiPlugin, EguiPrimaryContextPass, egui};
@@ -9,15 +27,39 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(EguiPlugin::default())
-            .add_systems(EguiPrimaryContextPass, ui_system);
+            .add_systems(EguiPrimaryContextPass, (ui_system, inspector_system, wind_system, terrain_system, perch_system, energy_system, leaders_system, formation_system, startle_system, traits_system, perception_system));
     }
 }
 
+/// Réglages d'affichage : orientation, caméra, surcouches et rendu.
+#[derive(SystemParam)]
+struct ViewSettings<'w> {
+    orientation_settings: ResMut<'w, OrientationSettings>,
+    camera_settings: ResMut<'w, CameraSettings>,
+    debug_overlay: ResMut<'w, DebugOverlaySettings>,
+    lod_settings: ResMut<'w, LodSettings>,
+    wing_settings: ResMut<'w, WingSettings>,
+}
+
+/// Réglages et mesures du pas de simulation.
+#[derive(SystemParam)]
+struct SimulationControls<'w> {
+    integration_settings: ResMut<'w, IntegrationSettings>,
+    collision_settings: ResMut<'w, CollisionSettings>,
+    collision_stats: ResMut<'w, CollisionStats>,
+    diagnostics: Res<'w, DiagnosticsStore>,
+}
+
 fn ui_system(
     mut contexts: EguiContexts,
     mut boid_settings: ResMut<BoidSettings>,
-    diagnostics: Res<DiagnosticsStore>,
+    mut wander_settings: ResMut<WanderSettings>,
+    mut steering_registry: ResMut<SteeringRegistry>,
+    view: ViewSettings,
+    simulation: SimulationControls,
 ) -> Result {
+    let ViewSettings { mut orientation_settings, mut camera_settings, mut debug_overlay, mut lod_settings, mut wing_settings } = view;
+    let SimulationControls { mut integration_settings, mut collision_settings, mut collision_stats, diagnostics } = simulation;
     egui::Window::new("Paramètres Boids").show(contexts.ctx_mut()?, |ui| {
         ui.heading("Forces");
 
@@ -41,6 +83,36 @@ fn ui_system(
                 .text("Attraction cible"),
         );
 
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut boid_settings.force_combination, ForceCombination::WeightedSum, "Somme");
+            ui.radio_value(&mut boid_settings.force_combination, ForceCombination::Prioritized, "Priorités");
+            ui.radio_value(
+                &mut boid_settings.force_combination,
+                ForceCombination::WeightedTruncated,
+                "Somme tronquée",
+            );
+        });
+
+        if boid_settings.force_combination != ForceCombination::WeightedSum {
+            ui.add(
+                egui::Slider::new(&mut boid_settings.max_force, 10.0..=5000.0)
+                    .logarithmic(true)
+                    .text("Force max"),
+            );
+        }
+
+        let ecs = boid_settings.simulation_backend == SimulationBackend::Ecs;
+        ui.collapsing("Comportements", |ui| {
+            // Le noyau compact n'évalue que les règles intégrées
+            for (index, entry) in steering_registry.entries_mut().iter_mut().enumerate() {
+                ui.add_enabled(ecs || index < BUILTIN_COUNT, egui::Checkbox::new(&mut entry.enabled, entry.behaviour.name()));
+            }
+            ui.add_enabled_ui(ecs, |ui| {
+                ui.add(egui::Slider::new(&mut wander_settings.weight, 0.0..=50.0).text("Errance"));
+                ui.add(egui::Slider::new(&mut wander_settings.rate, 0.05..=3.0).text("Variation de l'errance"));
+            });
+        });
+
         ui.separator();
         ui.heading("Distances");
 
@@ -80,8 +152,211 @@ fn ui_system(
         );
 
         ui.separator();
+        ui.heading("Orientation");
+
+        ui.add(
+            egui::Slider::new(&mut orientation_settings.banking_coeff, 0.0..=0.05)
+                .text("Inclinaison en virage"),
+        );
+
+        ui.add(
+            egui::Slider::new(&mut orientation_settings.max_bank_angle, 0.0..=90.0)
+                .text("Inclinaison max (°)"),
+        );
+
+        ui.add(
+            egui::Slider::new(&mut orientation_settings.smoothing, 0.0..=30.0)
+                .text("Lissage (0 : immédiat)"),
+        );
+
+        ui.add(
+            egui::Slider::new(&mut orientation_settings.max_turn_rate, 0.0..=720.0)
+                .text("Rotation max (°/s, 0 : illimitée)"),
+        );
+
+        ui.collapsing("Superpositions de débogage", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut debug_overlay.scope, OverlayScope::Selected, "Boid sélectionné");
+                ui.radio_value(&mut debug_overlay.scope, OverlayScope::All, "Tous les boids");
+            });
+            ui.checkbox(&mut debug_overlay.velocity, "Vélocité");
+            ui.checkbox(&mut debug_overlay.forces, "Forces de chaque comportement");
+            ui.checkbox(&mut debug_overlay.ranges, "Portées");
+            ui.checkbox(&mut debug_overlay.field_of_view, "Champ de vision");
+            ui.checkbox(&mut debug_overlay.group_targets, "Cibles des groupes");
+            ui.checkbox(&mut debug_overlay.obstacle_shells, "Zones d'influence des obstacles");
+            ui.checkbox(&mut debug_overlay.wind_field, "Champ de vent");
+            ui.add(
+                egui::Slider::new(&mut debug_overlay.vector_scale, 0.01..=2.0)
+                    .logarithmic(true)
+                    .text("Échelle des vecteurs"),
+            );
+        });
+
+        ui.separator();
+        ui.heading("Caméra");
+
+        egui::ComboBox::from_label("Mode")
+            .selected_text(camera_settings.mode.label())
+            .show_ui(ui, |ui| {
+                for mode in CameraMode::ALL {
+                    ui.selectable_value(&mut camera_settings.mode, mode, mode.label());
+                }
+            });
+        ui.label("C : mode suivant, N : boid suivant, G : groupe suivant");
+        ui.label("Molette : zoom, clic milieu : déplacer, double clic : recentrer");
+        ui.label("Clic sur un boid : l'inspecter");
+
+        ui.collapsing("Réglages caméra", |ui| {
+            let distance_range = camera_settings.orbit_distance_range.clone();
+            ui.add(
+                egui::Slider::new(&mut camera_settings.orbit_distance, distance_range.start..=distance_range.end)
+                    .logarithmic(true)
+                    .text("Distance"),
+            );
+            ui.add(egui::Slider::new(&mut camera_settings.zoom_speed, 0.01..=0.5).text("Vitesse zoom"));
+            ui.add(egui::Slider::new(&mut camera_settings.pan_speed, 0.0001..=0.01).text("Vitesse déplacement"));
+            ui.add(egui::Slider::new(&mut camera_settings.yaw_speed, 0.0005..=0.02).text("Vitesse lacet"));
+            ui.add(egui::Slider::new(&mut camera_settings.pitch_speed, 0.0005..=0.02).text("Vitesse tangage"));
+            ui.add(egui::Slider::new(&mut camera_settings.fly_speed, 5.0..=300.0).text("Vitesse vol libre"));
+            ui.add(egui::Slider::new(&mut camera_settings.follow_distance, 2.0..=100.0).text("Distance de suivi"));
+            ui.add(egui::Slider::new(&mut camera_settings.follow_height, -20.0..=50.0).text("Hauteur de suivi"));
+            ui.add(egui::Slider::new(&mut camera_settings.follow_smoothing, 0.5..=20.0).text("Lissage du suivi"));
+            ui.add(egui::Slider::new(&mut camera_settings.shot_duration, 1.0..=30.0).text("Durée d'un plan (s)"));
+
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Cible : ({:.0}, {:.0}, {:.0})",
+                    camera_settings.orbit_target.x, camera_settings.orbit_target.y, camera_settings.orbit_target.z
+                ));
+                if ui.button("Recentrer").clicked() {
+                    camera_settings.orbit_target = CameraSettings::default().orbit_target;
+                }
+            });
+        });
+
+        ui.separator();
+        ui.heading("Simulation");
+
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut boid_settings.simulation_backend, SimulationBackend::Ecs, "ECS");
+            ui.radio_value(
+                &mut boid_settings.simulation_backend,
+                SimulationBackend::Packed,
+                "Noyau compact",
+            );
+        });
+        if boid_settings.simulation_backend == SimulationBackend::Packed {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "Le noyau compact n'évalue que les cinq règles intégrées, avec un champ de vision conique. \
+                 Errance, vent, maintien d'altitude, perchage, fatigue, meneurs, formation, effarouchement, \
+                 traits individuels et perception sont ignorés.",
+            );
+        }
+
+        ui.collapsing("Intégration", |ui| {
+            ui.horizontal(|ui| {
+                for (integrator, label) in [
+                    (Integrator::ExplicitEuler, "Euler"),
+                    (Integrator::SemiImplicitEuler, "Euler semi-implicite"),
+                    (Integrator::Verlet, "Verlet"),
+                    (Integrator::Rk2, "RK2"),
+                ] {
+                    ui.radio_value(&mut integration_settings.integrator, integrator, label);
+                }
+            });
+            ui.add(
+                egui::Slider::new(&mut integration_settings.max_substep, 0.002..=0.1)
+                    .logarithmic(true)
+                    .text("Pas max (s)"),
+            );
+            ui.add(egui::Slider::new(&mut integration_settings.max_substeps, 1..=32).text("Sous-étapes max"));
+        });
+
+        ui.collapsing("Collisions", |ui| {
+            ui.checkbox(&mut collision_settings.enabled, "Empêcher la traversée des obstacles");
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut collision_settings.response, CollisionResponse::Slide, "Glisser");
+                ui.radio_value(&mut collision_settings.response, CollisionResponse::Reflect, "Rebondir");
+            });
+            if collision_settings.response == CollisionResponse::Reflect {
+                ui.add(egui::Slider::new(&mut collision_settings.restitution, 0.0..=1.0).text("Restitution"));
+            }
+            ui.add(egui::Slider::new(&mut collision_settings.boid_radius, 0.0..=5.0).text("Rayon d'un boid"));
+
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Contacts : {} cette frame, {} au total",
+                    collision_stats.frame, collision_stats.total
+                ));
+                if ui.button("Remettre à zéro").clicked() {
+                    collision_stats.total = 0;
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut boid_settings.render_mode, BoidRenderMode::Scene, "Scène glTF");
+            ui.radio_value(&mut boid_settings.render_mode, BoidRenderMode::Instanced, "Instancié");
+            ui.radio_value(&mut boid_settings.render_mode, BoidRenderMode::Animated, "Animé");
+            ui.radio_value(&mut boid_settings.render_mode, BoidRenderMode::Lod, "LOD");
+        });
+
+        if boid_settings.render_mode == BoidRenderMode::Lod {
+            ui.add(
+                egui::Slider::new(&mut lod_settings.near_distance, 20.0..=400.0)
+                    .text("Distance détaillée"),
+            );
+            ui.add(
+                egui::Slider::new(&mut lod_settings.far_distance, 20.0..=600.0)
+                    .text("Distance point"),
+            );
+            ui.add(
+                egui::Slider::new(&mut lod_settings.hysteresis, 0.0..=100.0)
+                    .text("Hystérésis"),
+            );
+            lod_settings.far_distance = lod_settings.far_distance.max(lod_settings.near_distance);
+        }
+
+        if matches!(boid_settings.render_mode, BoidRenderMode::Animated | BoidRenderMode::Lod) {
+            ui.collapsing("Ailes", |ui| {
+                ui.add(
+                    egui::Slider::new(&mut wing_settings.flap_frequency, 0.5..=8.0)
+                        .text("Fréquence de base (Hz)"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut wing_settings.speed_frequency_gain, 0.0..=10.0)
+                        .text("Gain vitesse (Hz)"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut wing_settings.climb_frequency_gain, 0.0..=0.5)
+                        .text("Gain montée"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut wing_settings.flap_amplitude, 0.0..=80.0)
+                        .text("Amplitude (°)"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut wing_settings.glide_angle, -20.0..=30.0)
+                        .text("Dièdre en plané (°)"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut wing_settings.glide_threshold, 0.0..=50.0)
+                        .text("Seuil de plané"),
+                );
+            });
+        }
 
-        ui.add(egui::Slider::new(&mut boid_settings.count, 1..=1000).text("Nombre de boids"));
+        let max_count = match boid_settings.simulation_backend {
+            SimulationBackend::Ecs => 1000,
+            SimulationBackend::Packed => 200_000,
+        };
+        ui.add(
+            egui::Slider::new(&mut boid_settings.count, 1..=max_count)
+                .logarithmic(max_count > 1000)
+                .text("Nombre de boids"),
+        );
 
         if let Some(value) = diagnostics
             .get(&FrameTimeDiagnosticsPlugin::FPS)
@@ -89,6 +364,396 @@ fn ui_system(
         {
             ui.label(format!("FPS: {:.1}", value));
         }
+
+        for (label, path) in [("Étape ECS", &ECS_STEP_TIME), ("Étape noyau compact", &PACKED_STEP_TIME)] {
+            if let Some(value) = diagnostics.get(path).and_then(|step| step.smoothed()) {
+                ui.label(format!("{}: {:.2} ms", label, value));
+            }
+        }
+    });
+    Ok(())
+}
+
+fn vector_label(ui: &mut egui::Ui, label: &str, vector: Vec3) {
+    ui.label(format!(
+        "{} : ({:.1}, {:.1}, {:.1}) | {:.1}",
+        label, vector.x, vector.y, vector.z, vector.length()
+    ));
+}
+
+fn inspector_system(
+    mut contexts: EguiContexts,
+    mut selected: ResMut<SelectedBoid>,
+    registry: Res<SteeringRegistry>,
+    boid_settings: Res<BoidSettings>,
+    group_leaders: Res<GroupLeaders>,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut camera_state: ResMut<CameraState>,
+) -> Result {
+    let (Some(entity), Some(report)) = (selected.entity, &selected.report) else {
+        return Ok(());
+    };
+
+    let mut deselect = false;
+    egui::Window::new("Inspecteur").show(contexts.ctx_mut()?, |ui| {
+        ui.label(format!("Boid {} — groupe {}", entity, report.group));
+        if group_leaders.is_leader(entity) {
+            ui.label("Meneur du groupe");
+        }
+        ui.label(format!("État : {}", flight_state_label(&report.flight_state)));
+        if let Some((energy, drafting)) = report.energy {
+            let drafting = if drafting { " (aspiration)" } else { "" };
+            ui.label(format!("Énergie : {:.0} %{}", energy * 100.0, drafting));
+        }
+        if let Some(traits) = report.traits {
+            ui.label(format!(
+                "Traits : vitesse ×{:.2}, vision ×{:.2}, portée ×{:.2}",
+                traits.speed, traits.field_of_view, traits.range
+            ));
+            ui.label(format!(
+                "Cohésion ×{:.2}, alignement ×{:.2}, séparation ×{:.2}",
+                traits.cohesion, traits.alignment, traits.separation
+            ));
+        }
+        vector_label(ui, "Position", report.position);
+        vector_label(ui, "Vélocité", report.velocity);
+
+        ui.separator();
+        ui.heading("Forces");
+        for (behaviour, force) in registry.breakdown(&report.forces) {
+            vector_label(ui, behaviour.name(), force);
+        }
+        vector_label(ui, "Somme", report.forces.total());
+        vector_label(ui, "Appliquée", registry.combine(&report.forces, &boid_settings));
+        if let Some(name) = registry.dominant(&report.forces) {
+            ui.label(format!("Règle dominante : {}", name));
+        }
+
+        ui.separator();
+        ui.heading("Voisins");
+        let neighbours = &report.neighbours;
+        ui.label(format!("En vue : {}", neighbours.in_view()));
+        ui.label(format!("Séparation (rouge) : {}", neighbours.separation.len()));
+        ui.label(format!("Alignement (jaune) : {}", neighbours.alignment.len()));
+        ui.label(format!("Cohésion (bleu) : {}", neighbours.cohesion.len()));
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("Suivre").clicked() {
+                camera_state.followed_boid = Some(entity);
+                camera_settings.mode = CameraMode::FollowBoid;
+            }
+            if ui.button("Désélectionner").clicked() {
+                deselect = true;
+            }
+        });
+    });
+
+    if deselect {
+        selected.entity = None;
+        selected.report = None;
+    }
+    Ok(())
+}
+
+// Contenu d'une fenêtre propre à la simulation ECS, grisé avec le noyau compact
+fn ecs_only(ui: &mut egui::Ui, boid_settings: &BoidSettings, add_contents: impl FnOnce(&mut egui::Ui)) {
+    let ecs = boid_settings.simulation_backend == SimulationBackend::Ecs;
+    if !ecs {
+        ui.colored_label(ui.visuals().warn_fg_color, "Ignoré par le noyau compact : passer en simulation ECS");
+    }
+    ui.add_enabled_ui(ecs, add_contents);
+}
+
+fn wind_system(
+    mut contexts: EguiContexts,
+    mut wind_field: ResMut<WindField>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Vent").default_open(false).show(contexts.ctx_mut()?, |ui| ecs_only(ui, &boid_settings, |ui| {
+        ui.checkbox(&mut wind_field.enabled, "Activer le vent");
+
+        ui.heading("Vent uniforme");
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut wind_field.uniform.x).speed(0.5).prefix("x : "));
+            ui.add(egui::DragValue::new(&mut wind_field.uniform.y).speed(0.5).prefix("y : "));
+            ui.add(egui::DragValue::new(&mut wind_field.uniform.z).speed(0.5).prefix("z : "));
+        });
+        ui.add(egui::Slider::new(&mut wind_field.gust_amplitude, 0.0..=1.0).text("Rafales"));
+        ui.add(egui::Slider::new(&mut wind_field.gust_frequency, 0.01..=2.0).text("Fréquence des rafales (Hz)"));
+
+        ui.heading("Tourbillon");
+        ui.add(egui::Slider::new(&mut wind_field.vortex_strength, -100.0..=100.0).text("Intensité"));
+        ui.add(egui::Slider::new(&mut wind_field.vortex_radius, 1.0..=100.0).text("Rayon du cœur"));
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut wind_field.vortex_center.x).speed(0.5).prefix("x : "));
+            ui.add(egui::DragValue::new(&mut wind_field.vortex_center.z).speed(0.5).prefix("z : "));
+        });
+
+        ui.heading("Turbulence");
+        ui.add(egui::Slider::new(&mut wind_field.turbulence_strength, 0.0..=200.0).text("Intensité"));
+        ui.add(
+            egui::Slider::new(&mut wind_field.turbulence_scale, 0.005..=0.2)
+                .logarithmic(true)
+                .text("Échelle spatiale"),
+        );
+        ui.add(egui::Slider::new(&mut wind_field.turbulence_speed, 0.0..=2.0).text("Évolution"));
+        ui.add(egui::DragValue::new(&mut wind_field.seed).prefix("Graine : "));
+    }));
+    Ok(())
+}
+
+fn terrain_system(
+    mut contexts: EguiContexts,
+    mut terrain_settings: ResMut<TerrainSettings>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Terrain").default_open(false).show(contexts.ctx_mut()?, |ui| {
+        ui.checkbox(&mut terrain_settings.enabled, "Relief et maintien d'altitude");
+        ui.add(egui::Slider::new(&mut terrain_settings.amplitude, 0.0..=50.0).text("Amplitude"));
+        ui.add(
+            egui::Slider::new(&mut terrain_settings.scale, 0.002..=0.1)
+                .logarithmic(true)
+                .text("Échelle"),
+        );
+        ui.add(egui::Slider::new(&mut terrain_settings.octaves, 1..=8).text("Octaves"));
+        ui.add(egui::DragValue::new(&mut terrain_settings.seed).prefix("Graine : "));
+
+        ui.separator();
+        // Le relief borne aussi le noyau compact, mais le maintien d'altitude est une règle ECS
+        ecs_only(ui, &boid_settings, |ui| {
+            ui.add(egui::Slider::new(&mut terrain_settings.altitude, 0.0..=80.0).text("Altitude au-dessus du sol"));
+            ui.add(egui::Slider::new(&mut terrain_settings.altitude_coeff, 0.0..=20.0).text("Maintien d'altitude"));
+        });
     });
     Ok(())
 }
+
+fn flight_state_label(flight_state: &FlightState) -> &'static str {
+    match flight_state {
+        FlightState::Flying => "en vol",
+        FlightState::Landing { .. } => "atterrissage",
+        FlightState::Perched { .. } => "posé",
+        FlightState::TakingOff { .. } => "envol",
+    }
+}
+
+fn perch_system(
+    mut contexts: EguiContexts,
+    mut perch_settings: ResMut<PerchSettings>,
+    mut startle_events: EventWriter<StartlePerchedEvent>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Perchage").default_open(false).show(contexts.ctx_mut()?, |ui| ecs_only(ui, &boid_settings, |ui| {
+        ui.checkbox(&mut perch_settings.enabled, "Autoriser les boids à se poser");
+        ui.add(egui::Slider::new(&mut perch_settings.landing_rate, 0.0..=1.0).text("Envie de se poser (/s)"));
+        ui.add(egui::Slider::new(&mut perch_settings.landing_speed, 1.0..=80.0).text("Vitesse d'approche"));
+        ui.add(egui::Slider::new(&mut perch_settings.min_rest, 0.0..=60.0).text("Repos min (s)"));
+        ui.add(egui::Slider::new(&mut perch_settings.max_rest, 0.0..=60.0).text("Repos max (s)"));
+        ui.add(egui::Slider::new(&mut perch_settings.takeoff_duration, 0.0..=5.0).text("Durée d'envol (s)"));
+
+        ui.label("Surfaces");
+        ui.checkbox(&mut perch_settings.perch_on_terrain, "Sol");
+        ui.checkbox(&mut perch_settings.perch_on_obstacles, "Sommet des obstacles");
+        ui.checkbox(&mut perch_settings.perch_on_lines, "Lignes de perche");
+
+        ui.separator();
+        ui.checkbox(&mut perch_settings.startle_cascade, "Envol en cascade");
+        ui.add(egui::Slider::new(&mut perch_settings.cascade_radius, 1.0..=50.0).text("Rayon de la cascade"));
+        ui.add(egui::Slider::new(&mut perch_settings.cascade_delay, 0.0..=2.0).text("Délai de réaction (s)"));
+        if ui.button("Effrayer les boids posés").clicked() {
+            startle_events.write(StartlePerchedEvent);
+        }
+    }));
+    Ok(())
+}
+
+fn energy_system(
+    mut contexts: EguiContexts,
+    mut energy_settings: ResMut<EnergySettings>,
+    energy_stats: Res<EnergyStats>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Énergie").default_open(false).show(contexts.ctx_mut()?, |ui| ecs_only(ui, &boid_settings, |ui| {
+        ui.checkbox(&mut energy_settings.enabled, "Fatigue");
+
+        ui.heading("Dépense");
+        ui.add(egui::Slider::new(&mut energy_settings.speed_drain, 0.0..=0.2).text("À vitesse max (/s)"));
+        ui.add(
+            egui::Slider::new(&mut energy_settings.acceleration_drain, 0.0..=0.001)
+                .logarithmic(true)
+                .text("Par unité d'accélération"),
+        );
+        ui.add(egui::Slider::new(&mut energy_settings.exhausted_speed_factor, 0.1..=1.0).text("Vitesse max à vide"));
+
+        ui.heading("Récupération");
+        ui.add(egui::Slider::new(&mut energy_settings.glide_recovery, 0.0..=0.5).text("En plané (/s)"));
+        ui.add(egui::Slider::new(&mut energy_settings.glide_threshold, 0.0..=100.0).text("Seuil de plané"));
+        ui.add(egui::Slider::new(&mut energy_settings.perched_recovery, 0.0..=1.0).text("Posé (/s)"));
+
+        ui.heading("Aspiration");
+        ui.add(egui::Slider::new(&mut energy_settings.draft_range, 1.0..=40.0).text("Portée"));
+        ui.add(egui::Slider::new(&mut energy_settings.draft_saving, 0.0..=1.0).text("Économie"));
+        ui.add(egui::Slider::new(&mut energy_settings.drafting_coeff, 0.0..=20.0).text("Recherche d'aspiration"));
+        ui.add(egui::Slider::new(&mut energy_settings.fatigue_cohesion_coeff, 0.0..=50.0).text("Regroupement des fatigués"));
+
+        ui.separator();
+        ui.label(format!("Énergie moyenne : {:.0} %", energy_stats.mean * 100.0));
+        ui.label(format!("Énergie minimale : {:.0} %", energy_stats.min * 100.0));
+        ui.label(format!("Épuisés : {:.0} %", energy_stats.exhausted * 100.0));
+        ui.label(format!("En aspiration : {:.0} %", energy_stats.drafting * 100.0));
+        for (group, mean) in energy_stats.group_means.iter().enumerate() {
+            ui.label(format!("Groupe {} : {:.0} %", group, mean * 100.0));
+        }
+    }));
+    Ok(())
+}
+
+fn leaders_system(
+    mut contexts: EguiContexts,
+    mut leader_settings: ResMut<LeaderSettings>,
+    group_leaders: Res<GroupLeaders>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Meneurs").default_open(false).show(contexts.ctx_mut()?, |ui| ecs_only(ui, &boid_settings, |ui| {
+        ui.checkbox(&mut leader_settings.enabled, "Meneurs par groupe");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut leader_settings.selection, LeaderSelection::FrontMost, "Les plus avancés");
+            ui.radio_value(&mut leader_settings.selection, LeaderSelection::Designated, "Désignés");
+        });
+        ui.add(egui::Slider::new(&mut leader_settings.leaders_per_group, 1..=10).text("Meneurs par groupe"));
+        ui.add(egui::Slider::new(&mut leader_settings.rotation_period, 0.0..=120.0).text("Relais (s, 0 = jamais)"));
+        ui.add(egui::Slider::new(&mut leader_settings.target_coeff, 0.0..=20.0).text("Attraction des meneurs"));
+        ui.add(egui::Slider::new(&mut leader_settings.follow_coeff, 0.0..=50.0).text("Suivi des meneurs"));
+        ui.checkbox(&mut leader_settings.show_leaders, "Afficher les meneurs");
+
+        if leader_settings.enabled {
+            ui.separator();
+            for (group, leaders) in group_leaders.groups.iter().enumerate() {
+                let names: Vec<String> = leaders.iter().map(|leader| leader.to_string()).collect();
+                ui.label(format!("Groupe {} : {}", group, names.join(", ")));
+            }
+        }
+    }));
+    Ok(())
+}
+
+fn formation_system(
+    mut contexts: EguiContexts,
+    mut formation_settings: ResMut<FormationSettings>,
+    formation_state: Res<FormationState>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Formation").default_open(false).show(contexts.ctx_mut()?, |ui| ecs_only(ui, &boid_settings, |ui| {
+        ui.checkbox(&mut formation_settings.enabled, "Vol en formation");
+        ui.horizontal_wrapped(|ui| {
+            ui.radio_value(&mut formation_settings.shape, FormationShape::V, "V");
+            ui.radio_value(&mut formation_settings.shape, FormationShape::Echelon, "Échelon");
+            ui.radio_value(&mut formation_settings.shape, FormationShape::LineAbreast, "Ligne de front");
+            ui.radio_value(&mut formation_settings.shape, FormationShape::SphereShell, "Sphère");
+            ui.radio_value(&mut formation_settings.shape, FormationShape::Grid, "Grille");
+        });
+        ui.add(egui::Slider::new(&mut formation_settings.spacing, 1.0..=30.0).text("Espacement"));
+        ui.add(egui::Slider::new(&mut formation_settings.arrival_radius, 1.0..=50.0).text("Rayon d'arrivée"));
+        ui.add(egui::Slider::new(&mut formation_settings.formation_coeff, 0.0..=50.0).text("Maintien en formation"));
+        ui.add(egui::Slider::new(&mut formation_settings.blend_time, 0.1..=10.0).text("Durée du fondu (s)"));
+        ui.checkbox(&mut formation_settings.show_slots, "Afficher les places");
+
+        ui.label(format!("Fondu : {:.0} %", formation_state.blend * 100.0));
+        ui.label("Avec les meneurs activés, la formation se cale sur le premier meneur du groupe");
+    }));
+    Ok(())
+}
+
+fn startle_system(
+    mut contexts: EguiContexts,
+    mut startle_settings: ResMut<StartleSettings>,
+    waves: Res<StartleWaves>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Effarouchement").default_open(false).show(contexts.ctx_mut()?, |ui| ecs_only(ui, &boid_settings, |ui| {
+        ui.checkbox(&mut startle_settings.enabled, "Vagues d'effarouchement");
+        ui.label("Clic droit sur un boid pour l'effrayer");
+
+        ui.heading("Fuite");
+        ui.add(egui::Slider::new(&mut startle_settings.escape_duration, 0.1..=5.0).text("Durée (s)"));
+        ui.add(egui::Slider::new(&mut startle_settings.escape_speed_factor, 1.0..=4.0).text("Vitesse max (×)"));
+        ui.add(egui::Slider::new(&mut startle_settings.escape_coeff, 0.0..=1000.0).text("Poussée"));
+        ui.add(egui::Slider::new(&mut startle_settings.refractory, 0.0..=20.0).text("Insensibilité (s)"));
+
+        ui.heading("Propagation");
+        ui.add(egui::Slider::new(&mut startle_settings.latency, 0.0..=1.0).text("Temps de réaction (s)"));
+        ui.add(egui::Slider::new(&mut startle_settings.latency_jitter, 0.0..=0.5).text("Variabilité (s)"));
+        ui.add(egui::Slider::new(&mut startle_settings.propagation_radius, 1.0..=50.0).text("Rayon"));
+
+        ui.heading("Déclencheurs");
+        ui.add(egui::Slider::new(&mut startle_settings.random_rate, 0.0..=1.0).text("Au hasard (/s)"));
+        ui.checkbox(&mut startle_settings.camera_threat, "La caméra effraie");
+        ui.add(egui::Slider::new(&mut startle_settings.camera_threat_radius, 1.0..=100.0).text("Rayon de la caméra"));
+        ui.checkbox(&mut startle_settings.show_waves, "Afficher l'agitation");
+
+        ui.separator();
+        ui.heading("Vagues");
+        for wave in waves.waves.iter().rev() {
+            let speed = wave.speed().map_or("—".to_string(), |speed| format!("{:.1} u/s", speed));
+            ui.label(format!(
+                "#{} : {} boids, front à {:.1} u, vitesse {}",
+                wave.id, wave.reached, wave.front_distance, speed
+            ));
+        }
+    }));
+    Ok(())
+}
+
+fn traits_system(
+    mut contexts: EguiContexts,
+    mut trait_settings: ResMut<TraitSettings>,
+    mut resample_events: EventWriter<ResampleTraitsEvent>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Variabilité").default_open(false).show(contexts.ctx_mut()?, |ui| ecs_only(ui, &boid_settings, |ui| {
+        ui.checkbox(&mut trait_settings.enabled, "Traits individuels");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut trait_settings.distribution, TraitDistribution::Uniform, "Uniforme");
+            ui.radio_value(&mut trait_settings.distribution, TraitDistribution::Normal, "Normale");
+        });
+        ui.add(egui::Slider::new(&mut trait_settings.speed_spread, 0.0..=1.0).text("Écart de vitesse"));
+        ui.add(egui::Slider::new(&mut trait_settings.field_of_view_spread, 0.0..=1.0).text("Écart de champ de vision"));
+        ui.add(egui::Slider::new(&mut trait_settings.range_spread, 0.0..=1.0).text("Écart de portée"));
+        ui.add(egui::Slider::new(&mut trait_settings.coeff_spread, 0.0..=1.0).text("Écart des coefficients"));
+
+        ui.label("Les traits sont tirés à l'apparition de chaque boid");
+        if ui.button("Retirer les traits de tous les boids").clicked() {
+            resample_events.write(ResampleTraitsEvent);
+        }
+    }));
+    Ok(())
+}
+
+fn perception_system(
+    mut contexts: EguiContexts,
+    mut perception: ResMut<PerceptionSettings>,
+    boid_settings: Res<BoidSettings>,
+) -> Result {
+    egui::Window::new("Perception").default_open(false).show(contexts.ctx_mut()?, |ui| ecs_only(ui, &boid_settings, |ui| {
+        ui.heading("Bruit");
+        ui.add(egui::Slider::new(&mut perception.position_noise, 0.0..=10.0).text("Sur la position"));
+        ui.add(egui::Slider::new(&mut perception.velocity_noise, 0.0..=30.0).text("Sur la vélocité"));
+
+        ui.heading("Perception limitée");
+        ui.checkbox(&mut perception.neighbour_occlusion, "Masquage par les voisins plus proches");
+        ui.add(egui::Slider::new(&mut perception.body_radius, 0.1..=5.0).text("Rayon apparent d'un boid"));
+        ui.add(egui::Slider::new(&mut perception.max_neighbours, 0..=30).text("Voisins perçus (0 = tous)"));
+        ui.checkbox(&mut perception.obstacle_occlusion, "Les obstacles bloquent la vue");
+
+        ui.heading("Champ de vision");
+        ui.checkbox(&mut perception.separate_vertical_fov, "Champ vertical distinct (le champ global devient horizontal)");
+        ui.add(egui::Slider::new(&mut perception.vertical_field_of_view, 10.0..=180.0).text("Champ vertical (°)"));
+        ui.add(egui::Slider::new(&mut perception.blind_spot, 0.0..=180.0).text("Angle mort arrière (°)"));
+        ui.add(egui::Slider::new(&mut perception.band_softness, 0.0..=20.0).text("Transition entre bandes"));
+
+        ui.heading("Temps de réaction");
+        ui.add(egui::Slider::new(&mut perception.reaction_delay, 0.0..=1.0).text("Retard (s)"));
+    }));
+    Ok(())
+}